@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked}};
 
-use crate::{constants::ESCROW_SEED, state::Escrow};
+use crate::{constants::ESCROW_SEED, instructions::transfer_checked_with_hook, state::Escrow};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -52,7 +52,8 @@ impl<'info> Make<'info> {
         Ok(())
     }
 
-    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+    /// `remaining_accounts` carries mint A's transfer hook accounts, if it has a hook.
+    pub fn deposit(&mut self, deposit: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
@@ -62,9 +63,10 @@ impl<'info> Make<'info> {
             mint: self.mint_a.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)?;
+        transfer_checked_with_hook(cpi_ctx, deposit, self.mint_a.decimals)?;
 
         Ok(())
     }
@@ -1,7 +1,9 @@
 pub mod make;
 pub mod refund;
 pub mod take;
+pub mod transfer;
 
 pub use make::*;
 pub use refund::*;
-pub use take::*;
\ No newline at end of file
+pub use take::*;
+pub use transfer::*;
\ No newline at end of file
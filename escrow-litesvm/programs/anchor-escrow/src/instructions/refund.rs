@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked, CloseAccount, close_account};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, CloseAccount, close_account};
 
-use crate::{state::Escrow, constants::ESCROW_SEED};
+use crate::{state::Escrow, constants::ESCROW_SEED, instructions::transfer_checked_with_hook};
 
 #[derive(Accounts)]
 pub struct Refund<'info> {
@@ -34,7 +34,7 @@ pub struct Refund<'info> {
 }
 
 impl<'info> Refund<'info> {
-    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+    pub fn refund_and_close_vault(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
         let signer_seeds: [&[&[u8]]; 1] = [&[
             ESCROW_SEED,
             self.maker.key.as_ref(),
@@ -51,9 +51,10 @@ impl<'info> Refund<'info> {
             authority: self.escrow.to_account_info(),
         };
 
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(cpi_context, self.vault.amount, self.mint_a.decimals)?;
+        transfer_checked_with_hook(cpi_context, self.vault.amount, self.mint_a.decimals)?;
 
         let cpi_program = self.token_program.to_account_info();
 
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, CloseAccount, close_account}};
 
-use crate::{state::Escrow, constants::{ESCROW_SEED, FIVE_DAYS_IN_SECONDS}, error::AppError};
+use crate::{state::Escrow, constants::{ESCROW_SEED, FIVE_DAYS_IN_SECONDS}, error::AppError, instructions::transfer_checked_with_hook};
 
 //Create context
 #[derive(Accounts)]
@@ -56,8 +56,9 @@ pub struct Take<'info> {
 //Deposit tokens from taker to maker
 //Transfer tokens from vault to taker
 //Close vault account
+//`remaining_accounts` carries the transfer hook accounts of either mint
 impl<'info> Take<'info> {
-    pub fn deposit(&mut self) -> Result<()> {
+    pub fn deposit(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
          require!(
             Clock::get().unwrap().unix_timestamp >= self.escrow.created_at + FIVE_DAYS_IN_SECONDS,
             AppError::TooEarlyToClaim,
@@ -71,12 +72,13 @@ impl<'info> Take<'info> {
             mint: self.mint_b.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
+        transfer_checked_with_hook(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
     }
 
-    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+    pub fn withdraw_and_close_vault(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
         let signer_seeds: [&[&[u8]]; 1] = [&[
             ESCROW_SEED,
             self.maker.key.as_ref(),
@@ -93,9 +95,10 @@ impl<'info> Take<'info> {
             mint: self.mint_a.to_account_info(),
         };
 
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(cpi_context, self.vault.amount, self.mint_a.decimals)?;
+        transfer_checked_with_hook(cpi_context, self.vault.amount, self.mint_a.decimals)?;
 
         let cpi_program = self.token_program.to_account_info();
 
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::spl_token_2022::onchain, token_interface::TransferChecked};
+
+/// `transfer_checked` that also works for Token-2022 mints with a transfer hook.
+///
+/// Anchor's `transfer_checked` only passes the four transfer accounts, so the hook program
+/// never sees its extra accounts. Here the context's remaining accounts are searched for the
+/// hook's extra accounts (as resolved by the client) and appended to the CPI. Mints without a
+/// hook ignore them.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -17,17 +17,22 @@ declare_id!("yRpcWL5c7CZkiDzjUrJKMaspZuPXG7xgE2ymE8L2xPQ");
 pub mod anchor_escrow {
     use super::*;
 
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
+    pub fn make<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Make<'info>>,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+    ) -> Result<()> {
         ctx.accounts.init_escrow(seed, receive, &ctx.bumps)?;
-        ctx.accounts.deposit(deposit)
+        ctx.accounts.deposit(deposit, ctx.remaining_accounts)
     }
 
-    pub fn refund(ctx: Context<Refund>) -> Result<()> {
-        ctx.accounts.refund_and_close_vault()
+    pub fn refund<'info>(ctx: Context<'_, '_, 'info, 'info, Refund<'info>>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault(ctx.remaining_accounts)
     }
 
-    pub fn take(ctx: Context<Take>) -> Result<()> {
-        ctx.accounts.deposit()?;
-        ctx.accounts.withdraw_and_close_vault()
+    pub fn take<'info>(ctx: Context<'_, '_, 'info, 'info, Take<'info>>) -> Result<()> {
+        ctx.accounts.deposit(ctx.remaining_accounts)?;
+        ctx.accounts.withdraw_and_close_vault(ctx.remaining_accounts)
     }
 }
\ No newline at end of file
@@ -0,0 +1,407 @@
+//! Off-chain resolution of the extra accounts a transfer hook asks for.
+//!
+//! Token 2022 only invokes a hook with the accounts that were passed to the transfer, so a
+//! client has to read the hook's `ExtraAccountMetaList`, derive each account it describes and
+//! append them to `transfer_checked`. This crate does that without knowing anything about a
+//! particular hook, so wallets don't need to hardcode seeds.
+//!
+//! Account data is read through a caller supplied function, which keeps the crate independent
+//! of any RPC client:
+//!
+//! ```ignore
+//! add_extra_accounts_for_transfer_checked(&mut ix, &hook_program_id, |key| {
+//!     rpc.get_account_data(key).ok()
+//! })?;
+//! ```
+
+use std::fmt;
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use spl_type_length_value::state::TlvStateBorrowed;
+
+/// Seed of the `ExtraAccountMetaList` PDA, fixed by the transfer hook interface.
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Instruction tag of Token 2022 `TransferChecked`.
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The mint's `ExtraAccountMetaList` account does not exist.
+    MissingMetaList(Pubkey),
+    /// The `ExtraAccountMetaList` could not be parsed.
+    InvalidMetaList(Pubkey),
+    /// A seed points at an account that is not in the instruction.
+    MissingAccount(u8),
+    /// A seed reads account data that does not exist or is too short.
+    MissingAccountData(Pubkey),
+    /// A seed reads instruction data past its end.
+    InvalidInstructionData,
+    /// The entry uses an address configuration this resolver does not support.
+    UnsupportedMeta(u8),
+    /// A PDA entry contains an uninitialized seed.
+    UninitializedSeed,
+    /// The instruction is not a Token 2022 `transfer_checked`.
+    NotTransferChecked,
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMetaList(key) => write!(f, "ExtraAccountMetaList {key} not found"),
+            Self::InvalidMetaList(key) => write!(f, "ExtraAccountMetaList {key} is invalid"),
+            Self::MissingAccount(index) => write!(f, "no account at index {index}"),
+            Self::MissingAccountData(key) => write!(f, "account data of {key} not available"),
+            Self::InvalidInstructionData => write!(f, "seed reads past the instruction data"),
+            Self::UnsupportedMeta(discriminator) => {
+                write!(f, "unsupported extra account meta {discriminator}")
+            }
+            Self::UninitializedSeed => write!(f, "uninitialized seed"),
+            Self::NotTransferChecked => write!(f, "instruction is not transfer_checked"),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+pub fn extra_account_metas_address(mint: &Pubkey, hook_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], hook_program_id).0
+}
+
+/// Resolves the extra accounts of the hook's `Execute` instruction for a transfer of `amount`
+/// from `source` to `destination`, in the order the `ExtraAccountMetaList` lists them.
+pub fn resolve_extra_account_metas<F>(
+    hook_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    mut fetch_account_data: F,
+) -> Result<Vec<AccountMeta>, ResolveError>
+where
+    F: FnMut(&Pubkey) -> Option<Vec<u8>>,
+{
+    let meta_list_address = extra_account_metas_address(mint, hook_program_id);
+    let meta_list_data = fetch_account_data(&meta_list_address)
+        .ok_or(ResolveError::MissingMetaList(meta_list_address))?;
+    let tlv_state = TlvStateBorrowed::unpack(&meta_list_data)
+        .map_err(|_| ResolveError::InvalidMetaList(meta_list_address))?;
+    let extra_metas = ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&tlv_state)
+        .map_err(|_| ResolveError::InvalidMetaList(meta_list_address))?;
+
+    // Seeds index into the Execute instruction, which grows as entries are resolved
+    let mut execute_accounts = vec![*source, *mint, *destination, *authority, meta_list_address];
+    let mut execute_data = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE.to_vec();
+    execute_data.extend_from_slice(&amount.to_le_bytes());
+
+    let mut resolved = Vec::with_capacity(extra_metas.len());
+    for extra_meta in extra_metas.iter() {
+        let pubkey = resolve_address(
+            extra_meta,
+            hook_program_id,
+            &execute_accounts,
+            &execute_data,
+            &mut fetch_account_data,
+        )?;
+        execute_accounts.push(pubkey);
+        resolved.push(AccountMeta {
+            pubkey,
+            is_signer: extra_meta.is_signer.into(),
+            is_writable: extra_meta.is_writable.into(),
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Appends the hook's extra accounts, the hook program and its `ExtraAccountMetaList` to a
+/// Token 2022 `transfer_checked` instruction, which is what Token 2022 needs to invoke the hook.
+pub fn add_extra_accounts_for_transfer_checked<F>(
+    instruction: &mut Instruction,
+    hook_program_id: &Pubkey,
+    fetch_account_data: F,
+) -> Result<(), ResolveError>
+where
+    F: FnMut(&Pubkey) -> Option<Vec<u8>>,
+{
+    // transfer_checked: [source, mint, destination, authority, ..signers], data: tag | amount | decimals
+    if instruction.data.len() != 10
+        || instruction.data[0] != TRANSFER_CHECKED_TAG
+        || instruction.accounts.len() < 4
+    {
+        return Err(ResolveError::NotTransferChecked);
+    }
+    let amount = u64::from_le_bytes(instruction.data[1..9].try_into().unwrap());
+    let [source, mint, destination, authority] =
+        [0, 1, 2, 3].map(|index| instruction.accounts[index].pubkey);
+
+    let extra_metas = resolve_extra_account_metas(
+        hook_program_id,
+        &source,
+        &mint,
+        &destination,
+        &authority,
+        amount,
+        fetch_account_data,
+    )?;
+
+    instruction.accounts.extend(extra_metas);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*hook_program_id, false));
+    instruction.accounts.push(AccountMeta::new_readonly(
+        extra_account_metas_address(&mint, hook_program_id),
+        false,
+    ));
+    Ok(())
+}
+
+fn resolve_address<F>(
+    extra_meta: &ExtraAccountMeta,
+    hook_program_id: &Pubkey,
+    accounts: &[Pubkey],
+    instruction_data: &[u8],
+    fetch_account_data: &mut F,
+) -> Result<Pubkey, ResolveError>
+where
+    F: FnMut(&Pubkey) -> Option<Vec<u8>>,
+{
+    // 0: fixed address, 1: PDA of the hook, 128 + i: PDA of the program at account index i
+    let program_id = match extra_meta.discriminator {
+        0 => return Ok(Pubkey::new_from_array(extra_meta.address_config)),
+        1 => *hook_program_id,
+        discriminator if discriminator >= 1 << 7 => {
+            let index = discriminator - (1 << 7);
+            *accounts
+                .get(index as usize)
+                .ok_or(ResolveError::MissingAccount(index))?
+        }
+        discriminator => return Err(ResolveError::UnsupportedMeta(discriminator)),
+    };
+
+    let seeds = Seed::unpack_address_config(&extra_meta.address_config)
+        .map_err(|_| ResolveError::UnsupportedMeta(extra_meta.discriminator))?;
+    let mut seed_bytes = Vec::with_capacity(seeds.len());
+    for seed in seeds {
+        seed_bytes.push(resolve_seed(
+            seed,
+            accounts,
+            instruction_data,
+            fetch_account_data,
+        )?);
+    }
+
+    let seed_slices: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+    Ok(Pubkey::find_program_address(&seed_slices, &program_id).0)
+}
+
+fn resolve_seed<F>(
+    seed: Seed,
+    accounts: &[Pubkey],
+    instruction_data: &[u8],
+    fetch_account_data: &mut F,
+) -> Result<Vec<u8>, ResolveError>
+where
+    F: FnMut(&Pubkey) -> Option<Vec<u8>>,
+{
+    let account = |index: u8| {
+        accounts
+            .get(index as usize)
+            .ok_or(ResolveError::MissingAccount(index))
+    };
+
+    match seed {
+        Seed::Literal { bytes } => Ok(bytes),
+        Seed::InstructionData { index, length } => {
+            let start = index as usize;
+            instruction_data
+                .get(start..start + length as usize)
+                .map(<[u8]>::to_vec)
+                .ok_or(ResolveError::InvalidInstructionData)
+        }
+        Seed::AccountKey { index } => Ok(account(index)?.to_bytes().to_vec()),
+        Seed::AccountData {
+            account_index,
+            data_index,
+            length,
+        } => {
+            let key = account(account_index)?;
+            let start = data_index as usize;
+            fetch_account_data(key)
+                .and_then(|data| data.get(start..start + length as usize).map(<[u8]>::to_vec))
+                .ok_or(ResolveError::MissingAccountData(*key))
+        }
+        Seed::Uninitialized => Err(ResolveError::UninitializedSeed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn meta_list(metas: &[ExtraAccountMeta]) -> Vec<u8> {
+        let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, metas).unwrap();
+        data
+    }
+
+    fn transfer_checked(
+        source: &Pubkey,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+    ) -> Instruction {
+        let mut data = vec![TRANSFER_CHECKED_TAG];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.push(9);
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+            data,
+        }
+    }
+
+    #[test]
+    fn resolves_every_seed_kind() {
+        let hook = Pubkey::new_unique();
+        let [source, mint, destination, authority, fixed] = [(); 5].map(|_| Pubkey::new_unique());
+        let destination_owner = Pubkey::new_unique();
+
+        let metas = [
+            // Literal + AccountKey, like the whitelist PDA of the source owner
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"whitelisted_user".to_vec(),
+                    },
+                    Seed::AccountKey { index: 3 },
+                ],
+                false,
+                false,
+            )
+            .unwrap(),
+            ExtraAccountMeta::new_with_pubkey(&fixed.to_bytes().into(), false, true).unwrap(),
+            // Owner of the destination token account, read from its data
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"whitelisted_user".to_vec(),
+                    },
+                    Seed::AccountData {
+                        account_index: 2,
+                        data_index: 32,
+                        length: 32,
+                    },
+                ],
+                false,
+                false,
+            )
+            .unwrap(),
+            ExtraAccountMeta::new_with_seeds(
+                &[Seed::InstructionData {
+                    index: 8,
+                    length: 8,
+                }],
+                false,
+                false,
+            )
+            .unwrap(),
+            // PDA of the fixed account added above (index 6), as an external program
+            ExtraAccountMeta::new_external_pda_with_seeds(
+                6,
+                &[Seed::AccountKey { index: 0 }],
+                false,
+                false,
+            )
+            .unwrap(),
+        ];
+
+        let mut destination_data = vec![0; 165];
+        destination_data[32..64].copy_from_slice(destination_owner.as_ref());
+        let accounts = HashMap::from([
+            (extra_account_metas_address(&mint, &hook), meta_list(&metas)),
+            (destination, destination_data),
+        ]);
+
+        let mut ix = transfer_checked(&source, &mint, &destination, &authority);
+        add_extra_accounts_for_transfer_checked(&mut ix, &hook, |key| accounts.get(key).cloned())
+            .unwrap();
+
+        let pda =
+            |seeds: &[&[u8]], program: &Pubkey| Pubkey::find_program_address(seeds, program).0;
+        let expected = [
+            AccountMeta::new_readonly(
+                pda(&[b"whitelisted_user", authority.as_ref()], &hook),
+                false,
+            ),
+            AccountMeta::new(fixed, false),
+            AccountMeta::new_readonly(
+                pda(&[b"whitelisted_user", destination_owner.as_ref()], &hook),
+                false,
+            ),
+            AccountMeta::new_readonly(pda(&[&42u64.to_le_bytes()], &hook), false),
+            AccountMeta::new_readonly(pda(&[source.as_ref()], &fixed), false),
+            AccountMeta::new_readonly(hook, false),
+            AccountMeta::new_readonly(extra_account_metas_address(&mint, &hook), false),
+        ];
+        assert_eq!(ix.accounts[4..], expected[..]);
+    }
+
+    #[test]
+    fn missing_meta_list_and_account_data() {
+        let hook = Pubkey::new_unique();
+        let [source, mint, destination, authority] = [(); 4].map(|_| Pubkey::new_unique());
+        let meta_list_address = extra_account_metas_address(&mint, &hook);
+
+        let mut ix = transfer_checked(&source, &mint, &destination, &authority);
+        assert_eq!(
+            add_extra_accounts_for_transfer_checked(&mut ix, &hook, |_| None),
+            Err(ResolveError::MissingMetaList(meta_list_address))
+        );
+
+        let metas = [ExtraAccountMeta::new_with_seeds(
+            &[Seed::AccountData {
+                account_index: 2,
+                data_index: 32,
+                length: 32,
+            }],
+            false,
+            false,
+        )
+        .unwrap()];
+        let data = meta_list(&metas);
+        let result = add_extra_accounts_for_transfer_checked(&mut ix, &hook, |key| {
+            (*key == meta_list_address).then(|| data.clone())
+        });
+        assert_eq!(result, Err(ResolveError::MissingAccountData(destination)));
+        // Nothing is appended on failure
+        assert_eq!(ix.accounts.len(), 4);
+    }
+
+    #[test]
+    fn rejects_other_instructions() {
+        let hook = Pubkey::new_unique();
+        let keys = [(); 4].map(|_| Pubkey::new_unique());
+        let mut ix = transfer_checked(&keys[0], &keys[1], &keys[2], &keys[3]);
+        ix.data[0] = 3;
+
+        assert_eq!(
+            add_extra_accounts_for_transfer_checked(&mut ix, &hook, |_| None),
+            Err(ResolveError::NotTransferChecked)
+        );
+    }
+}
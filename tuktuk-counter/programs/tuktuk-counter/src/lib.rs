@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 declare_id!("3aLmMcsCabAmYdJDSxnEu7nvZKihnE3sNfgFs6MaSC1z");
 
-mod state;
+pub mod state;
 mod instructions;
 pub use instructions::*;
 
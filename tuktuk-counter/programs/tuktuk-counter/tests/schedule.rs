@@ -0,0 +1,168 @@
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{instruction::Instruction as AnchorInstruction, system_program},
+        AccountDeserialize, InstructionData, ToAccountMetas,
+    },
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_address::Address,
+    solana_instruction::{account_meta::AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    std::str::FromStr,
+    tuktuk_counter::state::Counter,
+    tuktuk_mock::{
+        crank::{crank, queued_tasks},
+        task_pda, task_queue_authority_pda, TaskV0, TriggerV0,
+    },
+};
+
+// Built by `anchor build`
+const PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/tuktuk_counter.so"
+);
+// Built by `anchor build` in tuktuk-mock, deployed at TukTuk's address
+const TUKTUK_PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../../tuktuk-mock/target/deploy/tuktuk_mock.so"
+);
+/// The only key `schedule` accepts. Signatures are not verified in these tests, so the
+/// transactions can name it as a signer without its keypair.
+const SCHEDULER: &str = "AHYic562KhgtAEkb1rSesqS87dFYRcfXb4WwWus3Zc9C";
+const CRANK_REWARD: u64 = 1_000_001;
+const FEE: u64 = 5_000;
+
+#[test]
+fn scheduled_increment_runs_on_the_next_crank() {
+    let mut svm = setup();
+    let scheduler = Pubkey::from_str(SCHEDULER).unwrap();
+    svm.airdrop(&address(&scheduler), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+    let task_queue = Pubkey::new_unique();
+
+    send_as(&mut svm, &scheduler, &[initialize_ix(&scheduler)]).expect("initialize");
+    send_as(
+        &mut svm,
+        &scheduler,
+        &[schedule_ix(&scheduler, &task_queue, 0)],
+    )
+    .expect("schedule");
+
+    let task: TaskV0 = load(&svm, &task_pda(&task_queue, 0));
+    assert_eq!(task.trigger, TriggerV0::Now);
+    assert_eq!(task.crank_reward, CRANK_REWARD);
+    assert_eq!(load::<Counter>(&svm, &counter_pda()).count, 0);
+
+    let crank_turner = Keypair::new();
+    svm.airdrop(&crank_turner.pubkey(), LAMPORTS_PER_SOL)
+        .unwrap();
+    for (task, result) in crank(&mut svm, &crank_turner) {
+        result.unwrap_or_else(|failed| panic!("task {task}: {:?}", failed.err));
+    }
+
+    assert_eq!(load::<Counter>(&svm, &counter_pda()).count, 1);
+    assert!(queued_tasks(&svm).is_empty());
+    assert_eq!(
+        svm.get_balance(&crank_turner.pubkey()).unwrap(),
+        LAMPORTS_PER_SOL + CRANK_REWARD - FEE
+    );
+}
+
+fn setup() -> LiteSVM {
+    let mut svm = LiteSVM::new().with_sigverify(false);
+    for (program_id, path) in [
+        (tuktuk_counter::ID, PROGRAM_PATH),
+        (tuktuk_mock::ID, TUKTUK_PROGRAM_PATH),
+    ] {
+        let program = std::fs::read(path)
+            .unwrap_or_else(|_| panic!("{path} not found, run `anchor build` first"));
+        svm.add_program(address(&program_id), &program).unwrap();
+    }
+    svm
+}
+
+fn counter_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"counter"], &tuktuk_counter::ID).0
+}
+
+fn queue_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"queue_authority"], &tuktuk_counter::ID).0
+}
+
+fn initialize_ix(user: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: tuktuk_counter::ID,
+        accounts: tuktuk_counter::accounts::Initialize {
+            user: *user,
+            counter: counter_pda(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: tuktuk_counter::instruction::Initialize {}.data(),
+    }
+}
+
+fn schedule_ix(user: &Pubkey, task_queue: &Pubkey, task_id: u16) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: tuktuk_counter::ID,
+        accounts: tuktuk_counter::accounts::Schedule {
+            user: *user,
+            counter: counter_pda(),
+            task_queue: *task_queue,
+            task_queue_authority: task_queue_authority_pda(task_queue, &queue_authority_pda()),
+            task: task_pda(task_queue, task_id),
+            queue_authority: queue_authority_pda(),
+            system_program: system_program::ID,
+            tuktuk_program: tuktuk_mock::ID,
+        }
+        .to_account_metas(None),
+        data: tuktuk_counter::instruction::Schedule { task_id }.data(),
+    }
+}
+
+/// Sends `instructions` paid by `payer` with placeholder signatures.
+#[allow(clippy::result_large_err)]
+fn send_as(
+    svm: &mut LiteSVM,
+    payer: &Pubkey,
+    instructions: &[AnchorInstruction],
+) -> TransactionResult {
+    let instructions: Vec<Instruction> = instructions.iter().map(convert_instruction).collect();
+    let message = Message::new_with_blockhash(
+        &instructions,
+        Some(&address(payer)),
+        &svm.latest_blockhash(),
+    );
+    let mut tx = Transaction::new_unsigned(message);
+    tx.signatures = vec![Default::default(); tx.message.header.num_required_signatures as usize];
+    svm.send_transaction(tx)
+}
+
+fn load<T: AccountDeserialize>(svm: &LiteSVM, key: &Pubkey) -> T {
+    let account = svm.get_account(&address(key)).unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn convert_instruction(ix: &AnchorInstruction) -> Instruction {
+    Instruction {
+        program_id: address(&ix.program_id),
+        accounts: ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: address(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: ix.data.clone(),
+    }
+}
+
+fn address(pubkey: &Pubkey) -> Address {
+    Address::new_from_array(pubkey.to_bytes())
+}
@@ -2,6 +2,10 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const QUEUE_AUTHORITY_SEED: &[u8] = b"queue_authority";
+/// TukTuk prefixes a task's signer seeds with this and the task queue.
+pub const CUSTOM_SIGNER_SEED: &[u8] = b"custom";
+/// Kept on the scheduled payer to cover the interaction account's rent.
+pub const SCHEDULED_PAYER_LAMPORTS: u64 = 10_000_000;
 pub const AGENT: &str = "agent";
 pub const AGENT_DESC: &str = "solana agent for tuktuk gpt oracle";
 
@@ -4,4 +4,6 @@ use anchor_lang::prelude::*;
 pub enum ErrorCode {
     #[msg("Custom error message")]
     CustomError,
+    #[msg("Payer is neither the agent's owner nor its scheduled payer")]
+    UnauthorizedPayer,
 }
\ No newline at end of file
@@ -35,6 +35,7 @@ impl<'info> Initialize<'info> {
     pub fn create_llm_context(&mut self, bumps: &InitializeBumps) -> Result<()> {
         self.agent.set_inner(Agent {
             context: self.llm_context.key(),
+            scheduled_payer: None,
             bump: bumps.agent,
         });
 
@@ -2,20 +2,25 @@ use crate::{instruction, AGENT, AGENT_DESC};
 use anchor_lang::prelude::*;
 use solana_gpt_oracle::{cpi::accounts::InteractWithLlm, ContextAccount};
 
-use crate::{Agent, ID};
+use crate::{error::ErrorCode, Agent, ID};
 
 #[derive(Accounts)]
 pub struct Interact<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// CHECK: Owner of the agent; the payer itself, unless the payer is the agent's scheduled payer
+    pub user: UncheckedAccount<'info>,
+
     /// CHECK: Checked oracle id
     #[account(mut)]
     pub interaction: AccountInfo<'info>,
 
     #[account(
-        seeds = [AGENT.as_bytes(),  payer.key().as_ref()],
-        bump
+        seeds = [AGENT.as_bytes(),  user.key().as_ref()],
+        bump,
+        constraint = payer.key() == user.key()
+            || agent.scheduled_payer == Some(payer.key()) @ ErrorCode::UnauthorizedPayer,
     )]
     pub agent: Account<'info, Agent>,
 
@@ -1,5 +1,6 @@
 use anchor_lang::{
     prelude::{instruction::Instruction, *},
+    system_program::{transfer, Transfer},
     InstructionData,
 };
 use solana_gpt_oracle::ContextAccount;
@@ -9,18 +10,33 @@ use tuktuk_program::{
     TransactionSourceV0, TriggerV0,
 };
 
-use crate::{Agent, AGENT, QUEUE_AUTHORITY_SEED};
+use crate::{Agent, AGENT, CUSTOM_SIGNER_SEED, QUEUE_AUTHORITY_SEED, SCHEDULED_PAYER_LAMPORTS};
 
 #[derive(Accounts)]
 pub struct Schedule<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    /// CHECK: Checked oracle id
-    #[account(mut)]
+    /// CHECK: PDA TukTuk signs for when running the task; pays for the interaction
+    #[account(
+        mut,
+        seeds = [CUSTOM_SIGNER_SEED, task_queue.key().as_ref(), AGENT.as_bytes(), payer.key().as_ref()],
+        seeds::program = tuktuk_program.key(),
+        bump,
+    )]
+    pub scheduled_payer: UncheckedAccount<'info>,
+
+    /// CHECK: The scheduled payer's interaction, created by the oracle
+    #[account(
+        mut,
+        seeds = [b"interaction", scheduled_payer.key().as_ref(), context_account.key().as_ref()],
+        seeds::program = solana_gpt_oracle::ID,
+        bump,
+    )]
     pub interaction: AccountInfo<'info>,
 
     #[account(
+        mut,
         seeds = [AGENT.as_bytes(),  payer.key().as_ref()],
         bump
     )]
@@ -55,11 +71,30 @@ pub struct Schedule<'info> {
 }
 
 impl<'info> Schedule<'info> {
-    pub fn schedule(&self, task_id: u16, bumps: &ScheduleBumps) -> Result<()> {
+    pub fn schedule(&mut self, task_id: u16, bumps: &ScheduleBumps) -> Result<()> {
+        // Lets the task's interaction pay from the scheduled payer on this agent's behalf
+        self.agent.scheduled_payer = Some(self.scheduled_payer.key());
+
+        // TukTuk can only sign for its own PDAs, so the task pays from the scheduled payer
+        let top_up = SCHEDULED_PAYER_LAMPORTS.saturating_sub(self.scheduled_payer.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.scheduled_payer.to_account_info(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
         let interact_ix = Instruction {
             program_id: crate::ID,
             accounts: vec![
-                AccountMeta::new(self.payer.key(), false),
+                AccountMeta::new(self.scheduled_payer.key(), true),
+                AccountMeta::new_readonly(self.payer.key(), false),
                 AccountMeta::new(self.interaction.key(), false),
                 AccountMeta::new_readonly(self.agent.key(), false),
                 AccountMeta::new_readonly(self.context_account.key(), false),
@@ -69,7 +104,12 @@ impl<'info> Schedule<'info> {
             data: crate::instruction::InteractWithLlm {}.data(),
         };
 
-        let (compiled_tx, _) = compile_transaction(vec![interact_ix], vec![]).unwrap();
+        let signer_seeds = vec![vec![
+            AGENT.as_bytes().to_vec(),
+            self.payer.key().to_bytes().to_vec(),
+            vec![bumps.scheduled_payer],
+        ]];
+        let (compiled_tx, _) = compile_transaction(vec![interact_ix], signer_seeds).unwrap();
 
         queue_task_v0(
             CpiContext::new_with_signer(
@@ -3,9 +3,11 @@ use anchor_lang::prelude::*;
 #[account]
 pub struct Agent {
     pub context: Pubkey,
+    /// TukTuk signer PDA of the latest `schedule`, allowed to pay for this agent's interactions
+    pub scheduled_payer: Option<Pubkey>,
     pub bump: u8,
 }
 
 impl Agent {
-    pub const LEN: usize = 8 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + (1 + 32) + 1;
 }
@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { PublicKey } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
 import { TuktukGptOracle } from "../target/types/tuktuk_gpt_oracle";
 import { init as initTuktuk, taskQueueAuthorityKey } from "@helium/tuktuk-sdk";
@@ -44,14 +45,25 @@ describe("gpt-oracle", () => {
       ORACLE_PROGRAM_ID
     );
 
-  const getInteractionPda = (context: PublicKey) =>
+  const getInteractionPda = (
+    context: PublicKey,
+    payer: PublicKey = wallet.publicKey
+  ) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("interaction"), payer.toBuffer(), context.toBuffer()],
+      ORACLE_PROGRAM_ID
+    );
+
+  // TukTuk signs for this PDA when it runs the scheduled interaction
+  const getScheduledPayerPda = () =>
     PublicKey.findProgramAddressSync(
       [
-        Buffer.from("interaction"),
+        Buffer.from("custom"),
+        TASK_QUEUE.toBuffer(),
+        Buffer.from("agent"),
         wallet.publicKey.toBuffer(),
-        context.toBuffer(),
       ],
-      ORACLE_PROGRAM_ID
+      TUKTUK_PROGRAM_ID
     );
 
   describe("Initialization", () => {
@@ -105,6 +117,7 @@ describe("gpt-oracle", () => {
         .accountsPartial({
           interaction: interactionPda,
           payer: wallet.publicKey,
+          user: wallet.publicKey,
           systemProgram: SYSTEM_PROGRAM_ID,
           oracleProgram: ORACLE_PROGRAM_ID,
           agent: agentPda,
@@ -114,6 +127,32 @@ describe("gpt-oracle", () => {
 
       console.log("Interaction tx:", tx);
     });
+
+    it("Rejects a payer that does not own the agent", async () => {
+      const [agentPda] = getAgentPda();
+      const agentAccount = await program.account.agent.fetch(agentPda);
+      const stranger = Keypair.generate();
+
+      try {
+        await program.methods
+          .interactWithLlm()
+          .accountsPartial({
+            interaction: getInteractionPda(agentAccount.context, stranger.publicKey)[0],
+            payer: stranger.publicKey,
+            user: wallet.publicKey,
+            systemProgram: SYSTEM_PROGRAM_ID,
+            oracleProgram: ORACLE_PROGRAM_ID,
+            agent: agentPda,
+            contextAccount: agentAccount.context,
+          })
+          .signers([stranger])
+          .rpc();
+      } catch (err) {
+        assert.include(`${err}`, "UnauthorizedPayer");
+        return;
+      }
+      assert.fail("Interaction paid by a stranger should fail");
+    });
   });
 
   describe("Schedule", () => {
@@ -124,7 +163,8 @@ describe("gpt-oracle", () => {
 
       const agentAccount = await program.account.agent.fetch(agentPda);
       const llmContextPda = agentAccount.context;
-      const [interactionPda] = getInteractionPda(llmContextPda);
+      const [scheduledPayer] = getScheduledPayerPda();
+      const [interactionPda] = getInteractionPda(llmContextPda, scheduledPayer);
 
       // register queue authority if not already
       const tqAuthPda = taskQueueAuthorityKey(TASK_QUEUE, queueAuthority)[0];
@@ -183,6 +223,7 @@ describe("gpt-oracle", () => {
         .schedule(taskId)
         .accountsPartial({
           payer: wallet.publicKey,
+          scheduledPayer,
           interaction: interactionPda,
           agent: agentPda,
           contextAccount: llmContextPda,
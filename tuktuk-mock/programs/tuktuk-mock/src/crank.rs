@@ -0,0 +1,104 @@
+//! Off-chain crank turner for LiteSVM tests: finds the tasks queued on the mock, checks their
+//! triggers against the clock and runs the due ones with `run_task_v0`.
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::AccountMeta as AnchorAccountMeta,
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_account::ReadableAccount;
+use solana_address::Address;
+use solana_clock::Clock;
+use solana_instruction::{account_meta::AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::{TaskV0, TransactionSourceV0};
+
+/// Every task queued on the mock, by address, in the order the crank runs them: earliest
+/// trigger first, then oldest.
+pub fn queued_tasks(svm: &LiteSVM) -> Vec<(Pubkey, TaskV0)> {
+    let mut tasks: Vec<(Pubkey, TaskV0)> = svm
+        .accounts_db()
+        .inner
+        .iter()
+        .filter(|(_, account)| account.owner().to_bytes() == crate::ID.to_bytes())
+        .filter_map(|(address, account)| {
+            let task = TaskV0::try_deserialize(&mut account.data()).ok()?;
+            Some((Pubkey::new_from_array(address.to_bytes()), task))
+        })
+        .collect();
+    tasks.sort_by_key(|(key, task)| {
+        let fires_at = match task.trigger {
+            crate::TriggerV0::Now => i64::MIN,
+            crate::TriggerV0::Timestamp(timestamp) => timestamp,
+        };
+        (fires_at, task.queued_at, *key)
+    });
+    tasks
+}
+
+/// `run_task_v0` for `task`, paid by `crank_turner`. The compiled transaction's accounts are
+/// passed writable where it needs them; its signers are signed for by the mock.
+pub fn run_task_instruction(
+    crank_turner: &Pubkey,
+    task_key: &Pubkey,
+    task: &TaskV0,
+) -> Instruction {
+    let mut accounts = crate::accounts::RunTaskV0 {
+        crank_turner: *crank_turner,
+        rent_refund: task.rent_refund,
+        task: *task_key,
+    }
+    .to_account_metas(None);
+    if let TransactionSourceV0::CompiledV0(transaction) = &task.transaction {
+        accounts.extend(transaction.accounts.iter().enumerate().map(|(index, key)| {
+            if transaction.is_writable(index) {
+                AnchorAccountMeta::new(*key, false)
+            } else {
+                AnchorAccountMeta::new_readonly(*key, false)
+            }
+        }));
+    }
+
+    Instruction {
+        program_id: address(&crate::ID),
+        accounts: accounts
+            .into_iter()
+            .map(|meta| AccountMeta {
+                pubkey: address(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: crate::instruction::RunTaskV0 {}.data(),
+    }
+}
+
+/// Runs every task whose trigger has fired, each in its own transaction paid by
+/// `crank_turner`. Failed tasks stay queued, as with TukTuk, and are retried on the next turn.
+pub fn crank(svm: &mut LiteSVM, crank_turner: &Keypair) -> Vec<(Pubkey, TransactionResult)> {
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let crank_turner_key = Pubkey::new_from_array(crank_turner.pubkey().to_bytes());
+
+    queued_tasks(svm)
+        .into_iter()
+        .filter(|(_, task)| task.is_due(now))
+        .map(|(key, task)| {
+            let tx = Transaction::new_signed_with_payer(
+                &[run_task_instruction(&crank_turner_key, &key, &task)],
+                Some(&crank_turner.pubkey()),
+                &[crank_turner],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            svm.expire_blockhash();
+            (key, result)
+        })
+        .collect()
+}
+
+fn address(pubkey: &Pubkey) -> Address {
+    Address::new_from_array(pubkey.to_bytes())
+}
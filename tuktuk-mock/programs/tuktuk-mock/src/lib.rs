@@ -0,0 +1,312 @@
+//! Stand-in for the TukTuk program, deployed at TukTuk's address in LiteSVM tests.
+//!
+//! `queue_task_v0` takes the same accounts and arguments as TukTuk's and writes a `TaskV0`
+//! with the same layout, so programs that queue tasks can be tested without a devnet task
+//! queue. Task queues themselves are not modelled: any account is accepted as the queue, and
+//! only the address of its `task_queue_authority` is checked.
+//!
+//! `run_task_v0` plays the cron runner: it executes a due task's compiled transaction through
+//! CPI, as TukTuk does, then closes the task. The `crank` module (feature `crank`) finds and
+//! runs due tasks in a LiteSVM instance.
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+    system_program,
+};
+
+#[cfg(all(feature = "crank", not(target_os = "solana")))]
+pub mod crank;
+
+declare_id!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
+
+pub const TASK_SEED: &[u8] = b"task";
+pub const TASK_QUEUE_AUTHORITY_SEED: &[u8] = b"task_queue_authority";
+/// Prefix of the seeds TukTuk signs compiled transactions with.
+pub const CUSTOM_SIGNER_SEED: &[u8] = b"custom";
+
+/// Account TukTuk creates for task `id` of `task_queue`.
+pub fn task_pda(task_queue: &Pubkey, id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[TASK_SEED, task_queue.as_ref(), &id.to_le_bytes()], &ID).0
+}
+
+pub fn task_queue_authority_pda(task_queue: &Pubkey, queue_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            TASK_QUEUE_AUTHORITY_SEED,
+            task_queue.as_ref(),
+            queue_authority.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+#[program]
+pub mod tuktuk_mock {
+    use super::*;
+
+    pub fn queue_task_v0(ctx: Context<QueueTaskV0>, args: QueueTaskArgsV0) -> Result<()> {
+        ctx.accounts.queue_task_v0(ctx.bumps, args)
+    }
+
+    pub fn run_task_v0<'info>(ctx: Context<'_, '_, 'info, 'info, RunTaskV0<'info>>) -> Result<()> {
+        ctx.accounts.run_task_v0(ctx.remaining_accounts)
+    }
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Task trigger has not fired yet")]
+    TaskNotReady,
+    #[msg("Remote transactions are not supported by the mock")]
+    RemoteTransaction,
+    #[msg("Remaining accounts do not match the compiled transaction")]
+    AccountsMismatch,
+    #[msg("Signer seeds do not derive the compiled signer")]
+    InvalidSigner,
+}
+
+#[derive(Accounts)]
+#[instruction(args: QueueTaskArgsV0)]
+pub struct QueueTaskV0<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub queue_authority: Signer<'info>,
+    /// CHECK: TukTuk stores the queue authority here; the mock only checks the address.
+    #[account(
+        seeds = [
+            TASK_QUEUE_AUTHORITY_SEED,
+            task_queue.key().as_ref(),
+            queue_authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub task_queue_authority: UncheckedAccount<'info>,
+    /// CHECK: any account; the mock keeps no queue state.
+    #[account(mut)]
+    pub task_queue: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = TaskV0::space(&args),
+        seeds = [TASK_SEED, task_queue.key().as_ref(), &args.id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TaskV0>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Runs a due task. Remaining accounts are the compiled transaction's `accounts`, in order.
+#[derive(Accounts)]
+pub struct RunTaskV0<'info> {
+    #[account(mut)]
+    pub crank_turner: Signer<'info>,
+    /// CHECK: gets the task's rent back.
+    #[account(mut, address = task.rent_refund)]
+    pub rent_refund: UncheckedAccount<'info>,
+    #[account(mut, close = rent_refund)]
+    pub task: Account<'info, TaskV0>,
+}
+
+impl<'info> QueueTaskV0<'info> {
+    pub fn queue_task_v0(&mut self, bumps: QueueTaskV0Bumps, args: QueueTaskArgsV0) -> Result<()> {
+        let rent_amount = self.task.get_lamports();
+        // Held by the task until a crank turner runs it
+        let crank_reward = args.crank_reward.unwrap_or_default();
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.payer.to_account_info(),
+                    to: self.task.to_account_info(),
+                },
+            ),
+            crank_reward,
+        )?;
+
+        self.task.set_inner(TaskV0 {
+            task_queue: self.task_queue.key(),
+            rent_amount,
+            crank_reward,
+            id: args.id,
+            trigger: args.trigger,
+            rent_refund: self.payer.key(),
+            transaction: args.transaction,
+            queued_at: Clock::get()?.unix_timestamp,
+            bump_seed: bumps.task,
+            free_tasks: args.free_tasks,
+            description: args.description,
+        });
+
+        msg!("Queued task {}", args.id);
+        Ok(())
+    }
+}
+
+impl<'info> RunTaskV0<'info> {
+    pub fn run_task_v0(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.task.is_due(Clock::get()?.unix_timestamp),
+            ErrorCode::TaskNotReady
+        );
+        let TransactionSourceV0::CompiledV0(transaction) = &self.task.transaction else {
+            return err!(ErrorCode::RemoteTransaction);
+        };
+        require!(
+            remaining_accounts.len() == transaction.accounts.len()
+                && remaining_accounts
+                    .iter()
+                    .zip(&transaction.accounts)
+                    .all(|(account, key)| account.key == key),
+            ErrorCode::AccountsMismatch
+        );
+
+        let task_queue = self.task.task_queue;
+        let signer_seeds: Vec<Vec<&[u8]>> = transaction
+            .signer_seeds
+            .iter()
+            .map(|seeds| {
+                [CUSTOM_SIGNER_SEED, task_queue.as_ref()]
+                    .into_iter()
+                    .chain(seeds.iter().map(Vec::as_slice))
+                    .collect()
+            })
+            .collect();
+        for (seeds, signer) in signer_seeds.iter().zip(&transaction.accounts) {
+            let derived = Pubkey::create_program_address(seeds, &ID)
+                .map_err(|_| error!(ErrorCode::InvalidSigner))?;
+            require_keys_eq!(derived, *signer, ErrorCode::InvalidSigner);
+        }
+        let signer_seeds: Vec<&[&[u8]]> = signer_seeds.iter().map(Vec::as_slice).collect();
+
+        for compiled in &transaction.instructions {
+            let instruction = transaction.instruction(compiled);
+            let accounts: Vec<AccountInfo<'info>> = compiled
+                .accounts
+                .iter()
+                .chain([&compiled.program_id_index])
+                .map(|&index| remaining_accounts[index as usize].clone())
+                .collect();
+            invoke_signed(&instruction, &accounts, &signer_seeds)?;
+        }
+
+        let crank_reward = self.task.crank_reward;
+        self.task.sub_lamports(crank_reward)?;
+        self.crank_turner.add_lamports(crank_reward)?;
+
+        msg!("Ran task {}", self.task.id);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct TaskV0 {
+    pub task_queue: Pubkey,
+    pub rent_amount: u64,
+    pub crank_reward: u64,
+    pub id: u16,
+    pub trigger: TriggerV0,
+    pub rent_refund: Pubkey,
+    pub transaction: TransactionSourceV0,
+    pub queued_at: i64,
+    pub bump_seed: u8,
+    pub free_tasks: u8,
+    pub description: String,
+}
+
+impl TaskV0 {
+    pub fn is_due(&self, now: i64) -> bool {
+        match self.trigger {
+            TriggerV0::Now => true,
+            TriggerV0::Timestamp(timestamp) => now >= timestamp,
+        }
+    }
+
+    fn space(args: &QueueTaskArgsV0) -> usize {
+        let mut variable = Vec::new();
+        args.trigger.serialize(&mut variable).unwrap();
+        args.transaction.serialize(&mut variable).unwrap();
+        8 + 32 + 8 + 8 + 2 + 32 + 8 + 1 + 1 + (4 + args.description.len()) + variable.len()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QueueTaskArgsV0 {
+    pub id: u16,
+    pub trigger: TriggerV0,
+    pub transaction: TransactionSourceV0,
+    pub crank_reward: Option<u64>,
+    pub free_tasks: u8,
+    pub description: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerV0 {
+    Now,
+    Timestamp(i64),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionSourceV0 {
+    CompiledV0(CompiledTransactionV0),
+    RemoteV0 { url: String, signer: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompiledTransactionV0 {
+    pub num_rw_signers: u8,
+    pub num_ro_signers: u8,
+    pub num_rw: u8,
+    pub accounts: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstructionV0>,
+    pub signer_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+impl CompiledTransactionV0 {
+    fn num_signers(&self) -> usize {
+        (self.num_rw_signers + self.num_ro_signers) as usize
+    }
+
+    pub fn is_signer(&self, index: usize) -> bool {
+        index < self.num_signers()
+    }
+
+    /// Accounts are ordered writable signers, read-only signers, writable, read-only.
+    pub fn is_writable(&self, index: usize) -> bool {
+        let signers = self.num_signers();
+        index < self.num_rw_signers as usize
+            || (signers..signers + self.num_rw as usize).contains(&index)
+    }
+
+    pub fn instruction(&self, compiled: &CompiledInstructionV0) -> Instruction {
+        Instruction {
+            program_id: self.accounts[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| {
+                    let index = index as usize;
+                    AccountMeta {
+                        pubkey: self.accounts[index],
+                        is_signer: self.is_signer(index),
+                        is_writable: self.is_writable(index),
+                    }
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompiledInstructionV0 {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
@@ -1,4 +0,0 @@
-pub const INIT_CONFIG_SEED: &[u8] = b"config";
-pub const WHITELISTED_USER_SEED: &[u8] = b"whitelisted_user";
-pub const MINT_TOKEN_SEED: &[u8] = b"mint-token-admin";
-pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
@@ -1,91 +0,0 @@
-use std::cell::RefMut;
-
-use anchor_lang::prelude::*;
-use anchor_spl::{
-    token_2022::spl_token_2022::{
-        extension::{
-            transfer_hook::TransferHookAccount, BaseStateWithExtensionsMut,
-            PodStateWithExtensionsMut,
-        },
-        pod::PodAccount,
-    },
-    token_interface::{Mint, TokenAccount},
-};
-
-use crate::{constant::{EXTRA_ACCOUNT_METAS_SEED, WHITELISTED_USER_SEED}, error::ErrorCode, state::WhitelistedUser};
-
-#[derive(Accounts)]
-pub struct TransferHook<'info> {
-    #[account(
-        token::mint = mint,
-        token::authority = owner,
-    )]
-    pub source_token: InterfaceAccount<'info, TokenAccount>,
-    pub mint: InterfaceAccount<'info, Mint>,
-    #[account(
-        token::mint = mint,
-    )]
-    pub destination_token: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: source token account owner, can be SystemAccount or PDA owned by another program
-    pub owner: UncheckedAccount<'info>,
-    /// CHECK: ExtraAccountMetaList Account,
-    #[account(
-        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], 
-        bump
-    )]
-    pub extra_account_meta_list: UncheckedAccount<'info>,
-    
-    #[account(
-        seeds = [WHITELISTED_USER_SEED, source_token.owner.key().as_ref()],
-        bump= whitelisted_user.bump,
-    )]
-    pub whitelisted_user: Account<'info, WhitelistedUser>,
-}
-
-impl<'info> TransferHook<'info> {
-    pub fn transfer_hook(&mut self, _amount: u64) -> Result<()> {
-        self.check_is_transferring()?;
-
-        msg!("Source token owner: {}", self.source_token.owner);
-        msg!("Whitelisted User : {}", self.whitelisted_user.user);
-        msg!("Destination token owner: {}", self.destination_token.owner);
-
-        if self.whitelisted_user.user == self.source_token.owner {
-            msg!("Transfer allowed: The address is whitelisted");
-        } else {
-            panic!("TransferHook: Address is not whitelisted");
-        }
-        Ok(())
-    }
-
-    /// Checks if the transfer hook is being executed during a transfer operation.
-    fn check_is_transferring(&mut self) -> Result<()> {
-       // Ensure that the source token account has the transfer hook extension enabled
-
-        // Get the account info of the source token account
-        let source_token_info = self.source_token.to_account_info();
-        // Borrow the account data mutably
-        let mut account_data_ref: RefMut<&mut [u8]> = source_token_info.try_borrow_mut_data()?;
-
-        // Unpack the account data as a PodStateWithExtensionsMut
-        // This will allow us to access the extensions of the token account
-        // We use PodStateWithExtensionsMut because TokenAccount is a POD (Plain Old Data) type
-        // PodAccount: The base structure of a Token-2022 account (POD = Plain Old Data, meaning it's just bytes with no pointers)
-        // PodStateWithExtensionsMut: A wrapper that lets you access both:
-        // The base account data (balance, mint, owner, etc.)
-        // The extensions (extra features like Transfer Hook, Interest Bearing, etc.)
-        // unpack(): Deserializes the raw bytes into a structured format
-        let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(*account_data_ref)?;
-        // Get the TransferHookAccount extension
-        // Search for the TransferHookAccount extension in the token account
-        // The returning struct has a `transferring` field that indicates if the account is in the middle of a transfer operation
-        let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
-
-        // Check if the account is in the middle of a transfer operation
-        if !bool::from(account_extension.transferring) {
-            panic!("TransferHook: Not transferring");
-        }
-
-        Ok(())
-    }
-}
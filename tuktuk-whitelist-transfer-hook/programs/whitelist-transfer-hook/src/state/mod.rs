@@ -1,5 +0,0 @@
-pub mod config;
-pub mod whitelist;
-
-pub use config::*;
-pub use whitelist::*;
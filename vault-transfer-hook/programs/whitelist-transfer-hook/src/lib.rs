@@ -1,69 +0,0 @@
-#![allow(unexpected_cfgs)]
-#![allow(deprecated)]
-
-use anchor_lang::prelude::*;
-
-mod instructions;
-mod state;
-
-use instructions::*;
-
-use spl_discriminator::SplDiscriminate;
-use spl_tlv_account_resolution::state::ExtraAccountMetaList;
-use spl_transfer_hook_interface::instruction::ExecuteInstruction;
-
-declare_id!("DhzyDgCmmQzVC4vEcj2zRGUyN8Mt5JynfdGLKkBcRGaX");
-
-#[program]
-pub mod whitelist_transfer_hook {
-    use super::*;
-
-    pub fn initialize_whitelist(ctx: Context<InitializeWhitelist>) -> Result<()> {
-        ctx.accounts.initialize_whitelist(ctx.bumps)
-    }
-
-    pub fn add_to_whitelist(ctx: Context<WhitelistOperations>, user: Pubkey) -> Result<()> {
-        ctx.accounts.add_to_whitelist(user)
-    }
-
-    pub fn remove_from_whitelist(ctx: Context<WhitelistOperations>, user: Pubkey) -> Result<()> {
-        ctx.accounts.remove_from_whitelist(user)
-    }
-
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        ctx.accounts.initialize_vault(&ctx.bumps)
-    }
-
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        ctx.accounts.deposit(amount, &ctx.bumps)
-    }
-
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw(amount, &ctx.bumps)
-    }
-
-    pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
-        ctx.accounts.mint(amount, &ctx.bumps)
-    }
-
-    pub fn initialize_transfer_hook(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
-        msg!("Initializing Transfer Hook...");
-        let extra_account_metas = InitializeExtraAccountMetaList::extra_account_metas()?;
-
-        msg!("Extra Account Metas: {:?}", extra_account_metas);
-        msg!("Extra Account Metas Length: {}", extra_account_metas.len());
-
-        ExtraAccountMetaList::init::<ExecuteInstruction>(
-            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
-            &extra_account_metas,
-        )
-        .unwrap();
-
-        Ok(())
-    }
-
-    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
-    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
-        ctx.accounts.transfer_hook(amount)
-    }
-}
@@ -1,7 +0,0 @@
-pub mod vault;
-pub mod whitelist;
-pub mod error;
-
-pub use vault::*;
-pub use whitelist::*;
-pub use error::*;
@@ -0,0 +1,68 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+    system_program, InstructionData, ToAccountMetas,
+};
+use solana_sdk_ids::ed25519_program;
+
+use super::{config_pda, kyc_nonce_pda, whitelisted_user_pda};
+use crate::{ed25519, instructions::KycAttestation};
+
+pub fn set_kyc_provider_instruction(owner: &Pubkey, kyc_provider: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetKycProvider {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetKycProvider { kyc_provider }.data(),
+    }
+}
+
+/// Ed25519 precompile instruction checking `signature`, the KYC provider's signature over
+/// `attestation.message()`.
+pub fn attestation_signature_instruction(
+    attestation: &KycAttestation,
+    provider: &Pubkey,
+    signature: &[u8; 64],
+) -> Instruction {
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data: ed25519::instruction_data(provider, signature, &attestation.message()),
+    }
+}
+
+/// The two instructions to send, in one transaction signed by `attestation.user`.
+/// `self_register` fails with `ExpiringModeUnsupported` in `Expiring` mode.
+pub fn self_register_instructions(
+    attestation: &KycAttestation,
+    provider: &Pubkey,
+    signature: &[u8; 64],
+) -> [Instruction; 2] {
+    let user = attestation.user;
+    let register = Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SelfRegister {
+            user,
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(&user),
+            used_nonce: kyc_nonce_pda(&user, attestation.nonce),
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SelfRegister {
+            kyc: attestation.kyc,
+            nonce: attestation.nonce,
+            expires_at: attestation.expires_at,
+        }
+        .data(),
+    };
+
+    [
+        attestation_signature_instruction(attestation, provider, signature),
+        register,
+    ]
+}
@@ -0,0 +1,261 @@
+use std::{collections::HashSet, fmt, str::FromStr};
+
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::instruction::Instruction,
+    system_program, InstructionData, ToAccountMetas,
+};
+
+use super::{config_pda, whitelisted_user_pda};
+use crate::state::KycInfo;
+
+/// Largest serialized transaction the network accepts (`PACKET_DATA_SIZE`).
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError {
+    pub line: usize,
+    pub value: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: `{}` is not a valid address",
+            self.line, self.value
+        )
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// Reads addresses separated by commas and/or newlines.
+///
+/// Fields are trimmed of whitespace and quotes, blank fields are ignored, a leading
+/// header row is skipped and duplicates are dropped while keeping the original order.
+pub fn parse_addresses_csv(csv: &str) -> Result<Vec<Pubkey>, CsvError> {
+    let mut seen = HashSet::new();
+    let mut users = Vec::new();
+    let mut header_allowed = true;
+
+    for (index, line) in csv.lines().enumerate() {
+        let fields: Vec<&str> = line
+            .split(',')
+            .map(|field| field.trim().trim_matches('"').trim())
+            .filter(|field| !field.is_empty())
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        let parsed: Vec<Result<Pubkey, &str>> = fields
+            .iter()
+            .map(|field| Pubkey::from_str(field).map_err(|_| *field))
+            .collect();
+
+        if header_allowed && parsed.iter().all(|result| result.is_err()) {
+            header_allowed = false;
+            continue;
+        }
+        header_allowed = false;
+
+        for result in parsed {
+            let user = result.map_err(|value| CsvError {
+                line: index + 1,
+                value: value.to_string(),
+            })?;
+            if seen.insert(user) {
+                users.push(user);
+            }
+        }
+    }
+
+    Ok(users)
+}
+
+/// Every user in the batch gets the same `kyc` record.
+pub fn batch_add_instruction(authority: &Pubkey, users: &[Pubkey], kyc: &KycInfo) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: batch_accounts(authority, users),
+        data: crate::instruction::BatchAddToWhitelist {
+            users: users.to_vec(),
+            kyc: *kyc,
+        }
+        .data(),
+    }
+}
+
+/// `entries` are `(user, rent_payer)` pairs, with each entry's `rent_payer` field.
+pub fn batch_remove_instruction(authority: &Pubkey, entries: &[(Pubkey, Pubkey)]) -> Instruction {
+    let users: Vec<Pubkey> = entries.iter().map(|(user, _)| *user).collect();
+    let mut accounts = batch_accounts(authority, &[]);
+    for (user, rent_payer) in entries {
+        accounts.push(AccountMeta::new(whitelisted_user_pda(user), false));
+        accounts.push(AccountMeta::new(*rent_payer, false));
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: crate::instruction::BatchRemoveFromWhitelist { users }.data(),
+    }
+}
+
+/// Splits `users` into `batch_add_to_whitelist` instructions, each small enough to be
+/// sent as its own transaction signed only by `authority`.
+/// Not usable in `Expiring` mode, where entries are added one by one with `add_to_whitelist`.
+pub fn batch_add_instructions(
+    authority: &Pubkey,
+    users: &[Pubkey],
+    kyc: &KycInfo,
+) -> Vec<Instruction> {
+    chunk_instructions(authority, users, |authority, chunk| {
+        batch_add_instruction(authority, chunk, kyc)
+    })
+}
+
+/// Same as [`batch_add_instructions`] for `batch_remove_from_whitelist`.
+pub fn batch_remove_instructions(
+    authority: &Pubkey,
+    entries: &[(Pubkey, Pubkey)],
+) -> Vec<Instruction> {
+    chunk_instructions(authority, entries, batch_remove_instruction)
+}
+
+/// Size of a legacy transaction holding only `instruction`, with `payer` as fee payer.
+pub fn transaction_size(payer: &Pubkey, instruction: &Instruction) -> usize {
+    let mut keys = HashSet::from([*payer, instruction.program_id]);
+    let mut signers = HashSet::from([*payer]);
+    for meta in &instruction.accounts {
+        keys.insert(meta.pubkey);
+        if meta.is_signer {
+            signers.insert(meta.pubkey);
+        }
+    }
+
+    let signatures = compact_len(signers.len()) + 64 * signers.len();
+    let header = 3;
+    let account_keys = compact_len(keys.len()) + 32 * keys.len();
+    let blockhash = 32;
+    let instructions = compact_len(1)
+        + 1
+        + compact_len(instruction.accounts.len())
+        + instruction.accounts.len()
+        + compact_len(instruction.data.len())
+        + instruction.data.len();
+
+    signatures + header + account_keys + blockhash + instructions
+}
+
+fn batch_accounts(authority: &Pubkey, users: &[Pubkey]) -> Vec<AccountMeta> {
+    let mut accounts = crate::accounts::BatchWhitelistOperations {
+        authority: *authority,
+        config: config_pda(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        users
+            .iter()
+            .map(|user| AccountMeta::new(whitelisted_user_pda(user), false)),
+    );
+    accounts
+}
+
+fn chunk_instructions<T>(
+    authority: &Pubkey,
+    users: &[T],
+    build: impl Fn(&Pubkey, &[T]) -> Instruction,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut start = 0;
+
+    while start < users.len() {
+        let mut end = start + 1;
+        // Grow the chunk until one more user would push the transaction over the limit
+        while end < users.len()
+            && transaction_size(authority, &build(authority, &users[start..=end]))
+                <= MAX_TRANSACTION_SIZE
+        {
+            end += 1;
+        }
+        instructions.push(build(authority, &users[start..end]));
+        start = end;
+    }
+
+    instructions
+}
+
+fn compact_len(value: usize) -> usize {
+    match value {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_header_quotes_and_duplicates() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let csv = format!("address\n\"{a}\"\n  {b} , {a}\n\n");
+
+        assert_eq!(parse_addresses_csv(&csv).unwrap(), vec![a, b]);
+    }
+
+    #[test]
+    fn rejects_invalid_address_after_header() {
+        let a = Pubkey::new_unique();
+        let csv = format!("{a}\nnot-an-address\n");
+
+        let err = parse_addresses_csv(&csv).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.value, "not-an-address");
+    }
+
+    #[test]
+    fn chunks_fit_in_a_transaction_and_cover_every_user() {
+        let authority = Pubkey::new_unique();
+        let users: Vec<Pubkey> = (0..100).map(|_| Pubkey::new_unique()).collect();
+
+        let instructions = batch_add_instructions(&authority, &users, &KycInfo::default());
+        assert!(instructions.len() > 1);
+
+        let mut covered = Vec::new();
+        for instruction in &instructions {
+            assert!(transaction_size(&authority, instruction) <= MAX_TRANSACTION_SIZE);
+            // authority, config and system program come before the whitelist entries
+            covered.extend(instruction.accounts[3..].iter().map(|meta| meta.pubkey));
+        }
+        let expected: Vec<Pubkey> = users.iter().map(whitelisted_user_pda).collect();
+        assert_eq!(covered, expected);
+    }
+
+    #[test]
+    fn remove_chunks_pass_each_entry_with_its_rent_payer() {
+        let authority = Pubkey::new_unique();
+        let entries: Vec<(Pubkey, Pubkey)> = (0..50)
+            .map(|_| (Pubkey::new_unique(), Pubkey::new_unique()))
+            .collect();
+
+        let instructions = batch_remove_instructions(&authority, &entries);
+        assert!(instructions.len() > 1);
+
+        let mut covered = Vec::new();
+        for instruction in &instructions {
+            assert!(transaction_size(&authority, instruction) <= MAX_TRANSACTION_SIZE);
+            covered.extend(instruction.accounts[3..].iter().map(|meta| meta.pubkey));
+        }
+        let expected: Vec<Pubkey> = entries
+            .iter()
+            .flat_map(|(user, rent_payer)| [whitelisted_user_pda(user), *rent_payer])
+            .collect();
+        assert_eq!(covered, expected);
+    }
+}
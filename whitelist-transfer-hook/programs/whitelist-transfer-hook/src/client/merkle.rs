@@ -0,0 +1,153 @@
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, InstructionData,
+    ToAccountMetas,
+};
+
+use super::{config_pda, merkle_claim_pda};
+use crate::merkle::{hash_pair, leaf_hash};
+
+/// Merkle tree over a whitelist, matching the hashing in [`crate::merkle`].
+///
+/// Leaves are deduplicated and sorted, so the same set of addresses always yields the
+/// same root whatever order the list was in. An odd node at the end of a level is
+/// carried up unchanged.
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(users: &[Pubkey]) -> Self {
+        let mut leaves: Vec<[u8; 32]> = users.iter().map(leaf_hash).collect();
+        leaves.sort_unstable();
+        leaves.dedup();
+
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// `None` for an empty list.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.levels.last().unwrap().first().copied()
+    }
+
+    /// Sibling hashes from the leaf up, or `None` if `user` is not in the tree.
+    pub fn proof(&self, user: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.levels[0].binary_search(&leaf_hash(user)).ok()?;
+        let mut proof = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+pub fn set_merkle_root_instruction(
+    owner: &Pubkey,
+    merkle_root: Option<[u8; 32]>,
+    merkle_tier: u8,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetMerkleRoot {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetMerkleRoot {
+            merkle_root,
+            merkle_tier,
+        }
+        .data(),
+    }
+}
+
+pub fn claim_whitelist_instruction(user: &Pubkey, proof: Vec<[u8; 32]>) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ClaimWhitelist {
+            user: *user,
+            config: config_pda(),
+            merkle_claim: merkle_claim_pda(user),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::ClaimWhitelist { proof }.data(),
+    }
+}
+
+/// Add this next to a transfer from `user` to skip the `WhitelistedUser` account entirely.
+pub fn prove_membership_instruction(user: &Pubkey, proof: Vec<[u8; 32]>) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ProveMembership {
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::ProveMembership { user: *user, proof }.data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::verify_proof;
+
+    #[test]
+    fn every_member_has_a_valid_proof() {
+        for size in 1..=9 {
+            let users: Vec<Pubkey> = (0..size).map(|_| Pubkey::new_unique()).collect();
+            let tree = MerkleTree::new(&users);
+            let root = tree.root().unwrap();
+
+            for user in &users {
+                let proof = tree.proof(user).unwrap();
+                assert!(verify_proof(&root, user, &proof), "size {size}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_non_members_and_foreign_proofs() {
+        let users: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let tree = MerkleTree::new(&users);
+        let root = tree.root().unwrap();
+        let outsider = Pubkey::new_unique();
+
+        assert!(tree.proof(&outsider).is_none());
+        let proof = tree.proof(&users[0]).unwrap();
+        assert!(!verify_proof(&root, &outsider, &proof));
+    }
+
+    #[test]
+    fn root_ignores_order_and_duplicates() {
+        let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut shuffled = users.clone();
+        shuffled.reverse();
+        shuffled.push(users[1]);
+
+        assert_eq!(
+            MerkleTree::new(&users).root(),
+            MerkleTree::new(&shuffled).root()
+        );
+        assert_eq!(MerkleTree::new(&[]).root(), None);
+    }
+}
@@ -0,0 +1,435 @@
+//! Off-chain helpers for building whitelist transactions. Not compiled into the on-chain program.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::token_2022::spl_token_2022;
+use spl_tlv_account_resolution::account::ExtraAccountMeta;
+
+use crate::{
+    constant::{
+        EXTRA_ACCOUNT_METAS_SEED, INIT_CONFIG_SEED, KYC_NONCE_SEED, MERKLE_CLAIM_SEED,
+        MINT_TOKEN_SEED, QUEUE_AUTHORITY_SEED, TRADING_SCHEDULE_SEED, VAULT_AUTHORITY_SEED, VAULT_POSITION_SEED,
+        VAULT_SEED, VAULT_TOKEN_SEED, VAULT_WHITELIST_SEED, WHITELISTED_USER_SEED,
+    },
+    tuktuk,
+};
+
+pub mod attestation;
+pub mod batch;
+pub mod merkle;
+pub mod transfer_log;
+
+pub use transfer_hook_client::{
+    add_extra_accounts_for_transfer_checked, resolve_extra_account_metas, ResolveError,
+};
+
+/// Vault id the hook resolves for each mint, for `vault_pda`.
+pub use crate::constant::HOOK_VAULT_ID;
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[INIT_CONFIG_SEED], &crate::ID).0
+}
+
+/// ProgramData account of this program, which names the upgrade authority allowed to run
+/// `init_config`.
+pub fn program_data_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[crate::ID.as_ref()],
+        &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )
+    .0
+}
+
+pub fn whitelisted_user_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[WHITELISTED_USER_SEED, user.as_ref()], &crate::ID).0
+}
+
+/// Record of `user`'s Merkle claim.
+pub fn merkle_claim_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MERKLE_CLAIM_SEED, user.as_ref()], &crate::ID).0
+}
+
+/// Marks `nonce` as used for `user`'s `self_register`.
+pub fn kyc_nonce_pda(user: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[KYC_NONCE_SEED, user.as_ref(), &nonce.to_le_bytes()],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Mint created by `init_mint`.
+pub fn mint_pda() -> Pubkey {
+    Pubkey::find_program_address(&[MINT_TOKEN_SEED, config_pda().as_ref()], &crate::ID).0
+}
+
+pub fn extra_account_meta_list_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], &crate::ID).0
+}
+
+pub fn trading_schedule_pda() -> Pubkey {
+    Pubkey::find_program_address(&[TRADING_SCHEDULE_SEED], &crate::ID).0
+}
+
+/// Signs the program's `queue_task_v0` CPIs. Add it as a queue authority of the task queue.
+pub fn queue_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[QUEUE_AUTHORITY_SEED], &crate::ID).0
+}
+
+/// Vault of `mint` with `vault_id`. `VaultGated` transfers use the `HOOK_VAULT_ID` one.
+pub fn vault_pda(mint: &Pubkey, vault_id: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, mint.as_ref(), &vault_id.to_le_bytes()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn vault_authority_pda(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED, vault.as_ref()], &crate::ID).0
+}
+
+pub fn vault_token_account_pda(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_TOKEN_SEED, vault.as_ref()], &crate::ID).0
+}
+
+/// Legacy vault whitelist, drained by `migrate_vault_whitelist`.
+pub fn vault_whitelist_pda() -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_WHITELIST_SEED], &crate::ID).0
+}
+
+/// `user`'s place on the whitelist of `vault` and their deposited amount.
+pub fn vault_position_pda(vault: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VAULT_POSITION_SEED, vault.as_ref(), user.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn add_approved_program_instruction(owner: &Pubkey, program_id: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ApprovedProgramOperations {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::AddApprovedProgram {
+            program_id: *program_id,
+        }
+        .data(),
+    }
+}
+
+pub fn remove_approved_program_instruction(owner: &Pubkey, program_id: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ApprovedProgramOperations {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::RemoveApprovedProgram {
+            program_id: *program_id,
+        }
+        .data(),
+    }
+}
+
+pub fn set_task_queue_instruction(owner: &Pubkey, task_queue: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetTaskQueue {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetTaskQueue { task_queue }.data(),
+    }
+}
+
+/// Sets the grace period after expiry and how long before it `EntryExpiringSoon` fires.
+pub fn set_expiry_grace_instruction(
+    owner: &Pubkey,
+    grace_period: i64,
+    expiry_notice: i64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetExpiryGrace {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetExpiryGrace {
+            grace_period,
+            expiry_notice,
+        }
+        .data(),
+    }
+}
+
+/// Queues `user`'s `expire_user` as task `task_id` of `task_queue`, and its `notify_expiring`
+/// as task `notice_task_id` while the config has an expiry notice. The ids must be free.
+pub fn schedule_expiry_instruction(
+    authority: &Pubkey,
+    user: &Pubkey,
+    task_queue: &Pubkey,
+    task_id: u16,
+    notice_task_id: Option<u16>,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ScheduleExpiry {
+            authority: *authority,
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            task_queue: *task_queue,
+            task_queue_authority: tuktuk::task_queue_authority_pda(
+                task_queue,
+                &queue_authority_pda(),
+            ),
+            task: tuktuk::task_pda(task_queue, task_id),
+            notice_task: notice_task_id.map(|id| tuktuk::task_pda(task_queue, id)),
+            queue_authority: queue_authority_pda(),
+            system_program: system_program::ID,
+            tuktuk_program: tuktuk::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::ScheduleExpiry {
+            user: *user,
+            task_id,
+            notice_task_id,
+        }
+        .data(),
+    }
+}
+
+/// `notify_expiring` of `user`, as run by the crank. It needs no signer.
+pub fn notify_expiring_instruction(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::NotifyExpiring {
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::NotifyExpiring { user: *user }.data(),
+    }
+}
+
+/// Closes `user`'s expired entry, refunding `rent_payer` (the entry's `rent_payer` field) and
+/// paying the reap bounty to `reaper`.
+pub fn reap_expired_instruction(
+    reaper: &Pubkey,
+    user: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ReapExpired {
+            reaper: *reaper,
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            rent_payer: *rent_payer,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::ReapExpired { user: *user }.data(),
+    }
+}
+
+pub fn set_retention_instruction(
+    owner: &Pubkey,
+    retention_period: i64,
+    reap_bounty: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetRetention {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetRetention {
+            retention_period,
+            reap_bounty,
+        }
+        .data(),
+    }
+}
+
+/// Moves the first `users.len()` entries of the legacy vault whitelist, which must be `users`
+/// in order, into their `VaultPosition`s in `vault`, the mint's `HOOK_VAULT_ID` vault. Signed by
+/// the Config owner, who pays the positions' rent and gets the legacy account's back.
+pub fn migrate_vault_whitelist_instruction(
+    owner: &Pubkey,
+    vault: &Pubkey,
+    users: &[Pubkey],
+) -> Instruction {
+    let mut accounts = crate::accounts::MigrateVaultWhitelist {
+        owner: *owner,
+        config: config_pda(),
+        vault: *vault,
+        vault_token_account: vault_token_account_pda(vault),
+        whitelist: vault_whitelist_pda(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        users
+            .iter()
+            .map(|user| AccountMeta::new(vault_position_pda(vault, user), false)),
+    );
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: crate::instruction::MigrateVaultWhitelist {}.data(),
+    }
+}
+
+/// Halts transfers except between treasuries. Signed by the owner or an operator.
+pub fn pause_instruction(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::Pause {
+            authority: *authority,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::Pause {}.data(),
+    }
+}
+
+pub fn unpause_instruction(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::Unpause {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::Unpause {}.data(),
+    }
+}
+
+pub fn add_treasury_instruction(owner: &Pubkey, token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::TreasuryOperations {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::AddTreasury {
+            token_account: *token_account,
+        }
+        .data(),
+    }
+}
+
+pub fn remove_treasury_instruction(owner: &Pubkey, token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::TreasuryOperations {
+            owner: *owner,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::RemoveTreasury {
+            token_account: *token_account,
+        }
+        .data(),
+    }
+}
+
+/// Token 2022 `transfer_checked` of the program's mint with the hook's extra accounts
+/// resolved from the on-chain `ExtraAccountMetaList`. `fetch_account_data` returns the data
+/// of an account, or `None` if it does not exist.
+pub fn transfer_checked_instruction<F>(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fetch_account_data: F,
+) -> Result<Instruction, ResolveError>
+where
+    F: FnMut(&Pubkey) -> Option<Vec<u8>>,
+{
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        source,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        decimals,
+    )
+    .expect("valid token program id");
+    add_extra_accounts_for_transfer_checked(&mut instruction, &crate::ID, fetch_account_data)?;
+    Ok(instruction)
+}
+
+/// Rewrites the mint's `ExtraAccountMetaList`, signed by the mint's transfer hook authority (the
+/// Config owner for the program's own mint).
+/// Pass `InitializeExtraAccountMetaList::extra_account_metas()` to move an existing mint
+/// onto the program's current account layout, or `extra_account_metas_with_log(true)` if the
+/// mint is in `Config.logged_mints`.
+pub fn update_extra_account_meta_list_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::UpdateExtraAccountMetaList {
+            extra_account_meta_list: extra_account_meta_list_pda(mint),
+            mint: *mint,
+            authority: *authority,
+            system_program: system_program::ID,
+            config: Some(config_pda()),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::UpdateExtraAccountMetaList {
+            extra_account_metas: extra_account_metas
+                .iter()
+                .copied()
+                .map(Into::into)
+                .collect(),
+        }
+        .data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::InitializeExtraAccountMetaList;
+
+    #[test]
+    fn update_data_matches_the_interface_encoding() {
+        let metas = InitializeExtraAccountMetaList::extra_account_metas().unwrap();
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let interface = spl_transfer_hook_interface::instruction::update_extra_account_meta_list(
+            &crate::ID.to_bytes().into(),
+            &extra_account_meta_list_pda(&mint).to_bytes().into(),
+            &mint.to_bytes().into(),
+            &authority.to_bytes().into(),
+            &metas,
+        );
+
+        assert_eq!(
+            update_extra_account_meta_list_instruction(&authority, &mint, &metas).data,
+            interface.data
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, AccountDeserialize,
+    InstructionData, ToAccountMetas,
+};
+
+use super::{config_pda, extra_account_meta_list_pda};
+use crate::{
+    constant::TRANSFER_LOG_SEED,
+    state::{TransferLog, TransferLogEntry},
+};
+
+pub fn transfer_log_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TRANSFER_LOG_SEED, mint.as_ref()], &crate::ID).0
+}
+
+/// Creates the mint's TransferLog, paid by the owner. Logging starts with
+/// `set_transfer_log_instruction(.., true)`.
+pub fn init_transfer_log_instruction(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::InitTransferLog {
+            owner: *owner,
+            config: config_pda(),
+            mint: *mint,
+            transfer_log: transfer_log_pda(mint),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::InitTransferLog {}.data(),
+    }
+}
+
+pub fn set_transfer_log_instruction(owner: &Pubkey, mint: &Pubkey, enabled: bool) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::SetTransferLog {
+            owner: *owner,
+            config: config_pda(),
+            transfer_log: transfer_log_pda(mint),
+            extra_account_meta_list: extra_account_meta_list_pda(mint),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::SetTransferLog { enabled }.data(),
+    }
+}
+
+/// Entries of a TransferLog account, oldest first.
+pub fn decode_transfer_log(data: &[u8]) -> anchor_lang::Result<Vec<TransferLogEntry>> {
+    let log = TransferLog::try_deserialize(&mut &data[..])?;
+    Ok(log.iter().copied().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountSerialize;
+
+    use super::*;
+
+    fn entry(amount: u64) -> TransferLogEntry {
+        TransferLogEntry {
+            amount,
+            ..TransferLogEntry::default()
+        }
+    }
+
+    fn encode(log: &TransferLog) -> Vec<u8> {
+        let mut data = Vec::new();
+        log.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= TransferLog::LEN);
+        data
+    }
+
+    #[test]
+    fn decodes_entries_oldest_first_after_wraparound() {
+        let mut log = TransferLog {
+            mint: Pubkey::new_unique(),
+            head: 0,
+            total: 0,
+            entries: vec![],
+            bump: 255,
+        };
+        for amount in 0..3 {
+            log.append(entry(amount));
+        }
+        let amounts = |data: &[u8]| -> Vec<u64> {
+            decode_transfer_log(data)
+                .unwrap()
+                .iter()
+                .map(|entry| entry.amount)
+                .collect()
+        };
+        assert_eq!(amounts(&encode(&log)), [0, 1, 2]);
+
+        let logged = TransferLog::CAPACITY as u64 + 5;
+        for amount in 3..logged {
+            log.append(entry(amount));
+        }
+        let expected: Vec<u64> = (logged - TransferLog::CAPACITY as u64..logged).collect();
+        assert_eq!(log.total, logged);
+        assert_eq!(log.entries.len(), TransferLog::CAPACITY);
+        assert_eq!(amounts(&encode(&log)), expected);
+    }
+}
@@ -2,3 +2,17 @@ pub const INIT_CONFIG_SEED: &[u8] = b"config";
 pub const WHITELISTED_USER_SEED: &[u8] = b"whitelisted_user";
 pub const MINT_TOKEN_SEED: &[u8] = b"mint-token-admin";
 pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+pub const KYC_NONCE_SEED: &[u8] = b"kyc_nonce";
+pub const MERKLE_CLAIM_SEED: &[u8] = b"merkle_claim";
+pub const TRANSFER_LOG_SEED: &[u8] = b"transfer_log";
+pub const TRADING_SCHEDULE_SEED: &[u8] = b"trading_schedule";
+/// PDA that signs `queue_task_v0`; must be added as a queue authority of the task queue.
+pub const QUEUE_AUTHORITY_SEED: &[u8] = b"queue_authority";
+// Vault-gated mode
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+pub const VAULT_TOKEN_SEED: &[u8] = b"vault-token";
+pub const VAULT_WHITELIST_SEED: &[u8] = b"whitelist";
+pub const VAULT_POSITION_SEED: &[u8] = b"vault_position";
+/// Id of the vault the hook resolves for each mint; vaults with other ids are not gated on.
+pub const HOOK_VAULT_ID: u16 = 0;
@@ -0,0 +1,93 @@
+//! Layout of an Ed25519 precompile instruction carrying a single signature.
+//!
+//! The precompile only checks that the signature is valid for the public key and message it
+//! points at. Callers still have to check that those are the key and message they expect, and
+//! that the offsets point into the precompile instruction itself rather than another one.
+
+use anchor_lang::prelude::Pubkey;
+
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+/// Signature count and a padding byte.
+const HEADER_LEN: usize = 2;
+/// Seven little-endian u16 offsets.
+const OFFSETS_LEN: usize = 14;
+const DATA_START: usize = HEADER_LEN + OFFSETS_LEN;
+/// Instruction index meaning "this instruction".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Data for an Ed25519 precompile instruction verifying `signature` of `message` by `signer`.
+pub fn instruction_data(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + PUBKEY_LEN;
+    let message_offset = signature_offset + SIGNATURE_LEN;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for value in [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        public_key_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
+
+/// Signer and message of a single-signature precompile instruction whose offsets all point
+/// into its own data, or `None` for anything else.
+pub fn parse_instruction_data(data: &[u8]) -> Option<(Pubkey, &[u8])> {
+    if data.len() < DATA_START || data[0] != 1 {
+        return None;
+    }
+    let offset = |index: usize| {
+        let start = HEADER_LEN + index * 2;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let [signature_offset, signature_ix, public_key_offset, public_key_ix, message_offset, message_len, message_ix] =
+        [0, 1, 2, 3, 4, 5, 6].map(offset);
+
+    if [signature_ix, public_key_ix, message_ix] != [CURRENT_INSTRUCTION; 3] {
+        return None;
+    }
+    data.get(signature_offset as usize..signature_offset as usize + SIGNATURE_LEN)?;
+    let public_key =
+        data.get(public_key_offset as usize..public_key_offset as usize + PUBKEY_LEN)?;
+    let message =
+        data.get(message_offset as usize..message_offset as usize + message_len as usize)?;
+
+    Some((Pubkey::try_from(public_key).ok()?, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_what_it_builds() {
+        let signer = Pubkey::new_unique();
+        let data = instruction_data(&signer, &[7; 64], b"attestation");
+
+        assert_eq!(
+            parse_instruction_data(&data),
+            Some((signer, &b"attestation"[..]))
+        );
+    }
+
+    #[test]
+    fn rejects_offsets_into_other_instructions() {
+        let mut data = instruction_data(&Pubkey::new_unique(), &[7; 64], b"attestation");
+        // Point the message at instruction 0 instead of the precompile instruction itself
+        data[HEADER_LEN + 12..HEADER_LEN + 14].copy_from_slice(&0u16.to_le_bytes());
+
+        assert_eq!(parse_instruction_data(&data), None);
+        assert_eq!(parse_instruction_data(&data[..DATA_START - 1]), None);
+    }
+}
@@ -10,10 +10,114 @@ pub enum ErrorCode {
     ExtraAccountMetaError,
     #[msg("Failed to initialize extension")]
     ExtensionInitializationFailed,
-    #[msg("Unauthorized: Only admin can perform this action")]
+    #[msg("Unauthorized: Signer does not hold the required role")]
     Unauthorized,
     #[msg("Address is not whitelisted")]
     NotWhiteListed,
     #[msg("TransferHook: Not transferring")]
     NotTransferring,
+    #[msg("Signer is not the pending owner")]
+    NotPendingOwner,
+    #[msg("Operator is already registered")]
+    OperatorAlreadyAdded,
+    #[msg("Operator is not registered")]
+    OperatorNotFound,
+    #[msg("Operator limit reached")]
+    TooManyOperators,
+    #[msg("Batch must contain at least one user")]
+    EmptyBatch,
+    #[msg("Number of users does not match the number of whitelist accounts")]
+    BatchLengthMismatch,
+    #[msg("Whitelist account does not match the user's PDA")]
+    InvalidWhitelistAccount,
+    #[msg("Merkle root is not set")]
+    MerkleRootNotSet,
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Clawback destination must differ from the source")]
+    InvalidClawbackDestination,
+    #[msg("KYC tier is out of range")]
+    InvalidKycTier,
+    #[msg("Country must be an ISO 3166-1 alpha-2 code")]
+    InvalidCountryCode,
+    #[msg("Tier rules do not allow a transfer between these tiers")]
+    TierTransferNotAllowed,
+    #[msg("No KYC provider is configured")]
+    KycProviderNotSet,
+    #[msg("Missing or invalid KYC provider attestation")]
+    InvalidAttestation,
+    #[msg("KYC attestation has expired")]
+    AttestationExpired,
+    #[msg("Program is already approved")]
+    ProgramAlreadyApproved,
+    #[msg("Program is not approved")]
+    ProgramNotApproved,
+    #[msg("Approved program limit reached")]
+    TooManyApprovedPrograms,
+    #[msg("Transfers are paused")]
+    TransfersPaused,
+    #[msg("Treasury is already designated")]
+    TreasuryAlreadyAdded,
+    #[msg("Treasury is not designated")]
+    TreasuryNotFound,
+    #[msg("Treasury limit reached")]
+    TooManyTreasuries,
+    #[msg("User is not expired")]
+    NotExpired,
+    #[msg("Transfer must involve the vault token account")]
+    TransferMustTouchVault,
+    #[msg("Source owner is not whitelisted for vault deposit")]
+    SourceNotWhitelisted,
+    #[msg("Destination owner is not whitelisted for vault withdrawal")]
+    DestinationNotWhitelisted,
+    #[msg("Mint does not match the vault mint")]
+    InvalidVaultMint,
+    #[msg("Insufficient balance recorded for this whitelisted user")]
+    InsufficientWhitelistedBalance,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Transfer log limit reached")]
+    TooManyLoggedMints,
+    #[msg("Trading windows must be within the week and blackouts must end after they start")]
+    InvalidTradingSchedule,
+    #[msg("Transfers are outside trading hours")]
+    OutsideTradingHours,
+    #[msg("Transfers are suspended for a blackout period")]
+    TradingBlackout,
+    #[msg("Whitelist entry is expired or inactive")]
+    WhitelistEntryExpired,
+    #[msg("Whitelist entries only expire in Expiring mode")]
+    NotExpiringMode,
+    #[msg("Expiry must be in the future, within the maximum entry duration and later than the current one")]
+    InvalidExpiry,
+    #[msg("Task queue is not the configured one")]
+    WrongTaskQueue,
+    #[msg("A task queue is configured, so the TukTuk accounts and a task id are required")]
+    MissingExpiryTask,
+    #[msg("Whitelist entry is in its grace period and cannot receive tokens")]
+    EntryInGracePeriod,
+    #[msg("Entry is not within its expiry notice period")]
+    NotExpiringSoon,
+    #[msg("Entry has not been expired for the retention period")]
+    RetentionNotElapsed,
+    #[msg("Account is not the entry's rent payer")]
+    InvalidRentPayer,
+    #[msg("Reap bounty must leave the entry's rent exemption with the rent payer")]
+    InvalidReapBounty,
+    #[msg("Vault position still holds deposited tokens")]
+    PositionNotEmpty,
+    #[msg("Position account does not match the user's PDA")]
+    InvalidPositionAccount,
+    #[msg("Amount is too small to be worth a share or a token at the current share price")]
+    ZeroShares,
+    #[msg("Vault has no shares to distribute rewards to")]
+    NoVaultShares,
+    #[msg("Transfer log is not writable in the mint's ExtraAccountMetaList")]
+    TransferLogNotWritable,
+    #[msg("In Expiring mode, entries are only added with add_to_whitelist")]
+    ExpiringModeUnsupported,
+    #[msg("Vault token account holds fewer tokens than the vault's total assets")]
+    VaultUndercollateralized,
 }
@@ -0,0 +1,120 @@
+//! Audit trail for compliance actions on holders of the program's mint.
+
+use anchor_lang::prelude::*;
+
+use crate::state::KycInfo;
+
+/// A whitelist entry was created, by an admin or through a Merkle claim.
+#[event]
+pub struct UserWhitelisted {
+    pub user: Pubkey,
+    pub kyc: KycInfo,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// An `Expiring` mode entry got a later expiry and was reactivated.
+#[event]
+pub struct WhitelistRenewed {
+    pub user: Pubkey,
+    pub expiry_timestamp: i64,
+    pub renewed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// An `Expiring` mode entry is `expiry_notice` seconds or less from its expiry. Emitted by a
+/// task queued alongside the entry's `expire_user` task.
+#[event]
+pub struct EntryExpiringSoon {
+    pub user: Pubkey,
+    pub expiry_timestamp: i64,
+    /// End of the grace period, after which the user can no longer send either.
+    pub grace_ends_at: i64,
+    pub timestamp: i64,
+}
+
+/// An expired entry was closed after its retention period. `rent_payer` got the rent back,
+/// less the `bounty` paid to `reaped_by`.
+#[event]
+pub struct EntryReaped {
+    pub user: Pubkey,
+    pub rent_payer: Pubkey,
+    pub reaped_by: Pubkey,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+/// Transfers were halted, except between treasury accounts.
+#[event]
+pub struct TransfersPaused {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransfersUnpaused {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderFrozen {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderThawed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A transfer of a logged mint, emitted by the hook. The mint's TransferLog only keeps the
+/// most recent of these, so indexers build the full history from the events.
+#[event]
+pub struct TransferLogged {
+    pub mint: Pubkey,
+    pub source_owner: Pubkey,
+    pub destination_owner: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// New supply of the program's mint, minted by the Config PDA.
+#[event]
+pub struct TokensIssued {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Transfer fees harvested to the program's mint were paid out.
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Burned from `source` and re-minted to `destination`, so supply dips by `amount` between
+/// the two steps of the same instruction.
+#[event]
+pub struct ClawedBack {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,175 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        self as instructions_sysvar, load_instruction_at_checked,
+    },
+};
+use solana_sdk_ids::ed25519_program;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, KYC_NONCE_SEED, WHITELISTED_USER_SEED},
+    ed25519,
+    error::ErrorCode,
+    events::UserWhitelisted,
+    state::{Config, KycInfo, UsedKycNonce, WhitelistMode, WhitelistedUser},
+};
+
+/// Statement signed off-chain by the KYC provider. Its Borsh encoding, after
+/// [`KycAttestation::DOMAIN`], is the message of the Ed25519 precompile instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KycAttestation {
+    /// This program, so an attestation cannot be replayed against another deployment.
+    pub program_id: Pubkey,
+    pub user: Pubkey,
+    pub kyc: KycInfo,
+    pub nonce: u64,
+    /// Unix timestamp after which the attestation can no longer be used.
+    pub expires_at: i64,
+}
+
+impl KycAttestation {
+    pub const DOMAIN: &'static [u8] = b"whitelist-transfer-hook:kyc-attestation:";
+
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Self::DOMAIN.to_vec();
+        // Writing to a Vec cannot fail
+        self.serialize(&mut message).unwrap();
+        message
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetKycProvider<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// The user registers themselves with an attestation from the KYC provider and pays the rent.
+/// The Ed25519 precompile instruction verifying the provider's signature must be in the same
+/// transaction. Not available in `Expiring` mode, since the attestation carries no expiry.
+#[derive(Accounts)]
+#[instruction(kyc: KycInfo, nonce: u64)]
+pub struct SelfRegister<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mode != WhitelistMode::Expiring @ ErrorCode::ExpiringModeUnsupported,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = user,
+        space = WhitelistedUser::LEN,
+        seeds = [WHITELISTED_USER_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+    /// `init` fails if the nonce was already used by this user
+    #[account(
+        init,
+        payer = user,
+        space = UsedKycNonce::LEN,
+        seeds = [KYC_NONCE_SEED, user.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub used_nonce: Account<'info, UsedKycNonce>,
+    /// CHECK: Instructions sysvar, used to find the Ed25519 precompile instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetKycProvider<'info> {
+    /// Attestations signed by a previous provider stop working immediately.
+    pub fn set_kyc_provider(&mut self, kyc_provider: Option<Pubkey>) -> Result<()> {
+        self.config.kyc_provider = kyc_provider;
+
+        msg!("KYC provider updated: {:?}", kyc_provider);
+        Ok(())
+    }
+}
+
+impl<'info> SelfRegister<'info> {
+    pub fn self_register(
+        &mut self,
+        bumps: SelfRegisterBumps,
+        kyc: KycInfo,
+        nonce: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let provider = self
+            .config
+            .kyc_provider
+            .ok_or(error!(ErrorCode::KycProviderNotSet))?;
+        kyc.validate()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= expires_at, ErrorCode::AttestationExpired);
+
+        let user = self.user.key();
+        let attestation = KycAttestation {
+            program_id: crate::ID,
+            user,
+            kyc,
+            nonce,
+            expires_at,
+        };
+        require!(
+            self.has_provider_signature(&provider, &attestation.message())?,
+            ErrorCode::InvalidAttestation
+        );
+
+        self.used_nonce.set_inner(UsedKycNonce {
+            user,
+            nonce,
+            bump: bumps.used_nonce,
+        });
+        self.whitelisted_user.set_inner(WhitelistedUser {
+            user,
+            kyc,
+            added_by: provider,
+            rent_payer: user,
+            added_at: now,
+            is_active: true,
+            expiry_timestamp: self.config.entry_expiry(now),
+            bump: bumps.whitelisted_user,
+        });
+
+        emit!(UserWhitelisted {
+            user,
+            kyc,
+            added_by: provider,
+            timestamp: now,
+        });
+
+        msg!("Self-registered with a KYC attestation. User: {}", user);
+        Ok(())
+    }
+
+    /// Ed25519 precompile instruction anywhere in this transaction over `message` by `provider`.
+    /// The runtime has already rejected the transaction if its signature is invalid.
+    fn has_provider_signature(&self, provider: &Pubkey, message: &[u8]) -> Result<bool> {
+        let sysvar = self.instructions_sysvar.to_account_info();
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, &sysvar) {
+            index += 1;
+            if ix.program_id != ed25519_program::ID {
+                continue;
+            }
+            if ed25519::parse_instruction_data(&ix.data)
+                .is_some_and(|(signer, signed)| signer == *provider && signed == message)
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,186 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, WHITELISTED_USER_SEED},
+    error::ErrorCode,
+    events::UserWhitelisted,
+    state::{Config, KycInfo, WhitelistMode, WhitelistedUser},
+};
+
+/// Whitelist entries are passed in `remaining_accounts`, one per user and in the same order.
+/// Batch removes pass each entry followed by its `rent_payer`, which gets the rent back.
+/// Batch adds are rejected in `Expiring` mode, where each entry needs its own expiry tasks.
+#[derive(Accounts)]
+pub struct BatchWhitelistOperations<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BatchWhitelistOperations<'info> {
+    pub fn batch_add_to_whitelist(
+        &mut self,
+        users: &[Pubkey],
+        kyc: KycInfo,
+        entries: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            self.config.mode != WhitelistMode::Expiring,
+            ErrorCode::ExpiringModeUnsupported
+        );
+        require!(!users.is_empty(), ErrorCode::EmptyBatch);
+        require_eq!(users.len(), entries.len(), ErrorCode::BatchLengthMismatch);
+        kyc.validate()?;
+        let added_by = self.authority.key();
+        let added_at = Clock::get()?.unix_timestamp;
+
+        for (user, entry) in users.iter().zip(entries) {
+            let (expected, bump) =
+                Pubkey::find_program_address(&[WHITELISTED_USER_SEED, user.as_ref()], &crate::ID);
+            require_keys_eq!(entry.key(), expected, ErrorCode::InvalidWhitelistAccount);
+
+            // Re-running a batch should not fail because some users were already added
+            if entry.owner == &crate::ID {
+                msg!("Already whitelisted. User: {}", user);
+                continue;
+            }
+
+            self.create_entry(entry, user, bump)?;
+
+            let mut data = entry.try_borrow_mut_data()?;
+            WhitelistedUser {
+                user: *user,
+                kyc,
+                added_by,
+                rent_payer: added_by,
+                added_at,
+                is_active: true,
+                expiry_timestamp: self.config.entry_expiry(added_at),
+                bump,
+            }
+            .try_serialize(&mut &mut data[..])?;
+
+            emit!(UserWhitelisted {
+                user: *user,
+                kyc,
+                added_by,
+                timestamp: added_at,
+            });
+            msg!("Added to whitelist. User: {}", user);
+        }
+
+        Ok(())
+    }
+
+    pub fn batch_remove_from_whitelist(
+        &mut self,
+        users: &[Pubkey],
+        accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!users.is_empty(), ErrorCode::EmptyBatch);
+        require_eq!(users.len() * 2, accounts.len(), ErrorCode::BatchLengthMismatch);
+
+        for (user, pair) in users.iter().zip(accounts.chunks_exact(2)) {
+            let (entry, rent_payer) = (&pair[0], &pair[1]);
+            let (expected, _) =
+                Pubkey::find_program_address(&[WHITELISTED_USER_SEED, user.as_ref()], &crate::ID);
+            require_keys_eq!(entry.key(), expected, ErrorCode::InvalidWhitelistAccount);
+
+            // Checks the owner and discriminator before handing the rent back
+            let whitelisted_user = Account::<WhitelistedUser>::try_from(entry)?;
+            require_keys_eq!(
+                rent_payer.key(),
+                whitelisted_user.rent_payer,
+                ErrorCode::InvalidRentPayer
+            );
+            whitelisted_user.close(rent_payer.clone())?;
+
+            msg!("Remove from whitelist. User: {}", user);
+        }
+
+        Ok(())
+    }
+
+    fn create_entry(&self, entry: &AccountInfo<'info>, user: &Pubkey, bump: u8) -> Result<()> {
+        create_program_account(
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+            entry,
+            WhitelistedUser::LEN,
+            &[&[WHITELISTED_USER_SEED, user.as_ref(), &[bump]]],
+        )
+    }
+}
+
+/// Creates the PDA `account` owned by this program, paid by `payer`. Same steps as Anchor's
+/// `init`, including the case where someone pre-funded the PDA.
+pub(crate) fn create_program_account<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    if account.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
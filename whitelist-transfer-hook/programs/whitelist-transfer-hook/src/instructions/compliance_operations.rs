@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{
+        burn, freeze_account, mint_to, thaw_account, Burn, FreezeAccount, MintTo, ThawAccount,
+        Token2022,
+    },
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, MINT_TOKEN_SEED},
+    error::ErrorCode,
+    events::{ClawedBack, HolderFrozen, HolderThawed},
+    state::Config,
+};
+
+/// Freeze and thaw are signed by the Config PDA, the freeze authority of the program's mint.
+#[derive(Accounts)]
+pub struct HolderOperations<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [MINT_TOKEN_SEED, config.key().as_ref()],
+        bump,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Token 2022 would call back into this program's transfer hook on a transfer, which the
+/// runtime rejects as reentrancy. So the Config PDA burns from the holder as permanent
+/// delegate and mints the same amount to the destination as mint authority. Total supply is
+/// unchanged once the instruction completes, but a clawback shows up as a burn and a mint
+/// rather than a transfer, which indexers tracking supply should expect.
+#[derive(Accounts)]
+pub struct Clawback<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [MINT_TOKEN_SEED, config.key().as_ref()],
+        bump,
+        mint::authority = config,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = destination_token.key() != source_token.key() @ ErrorCode::InvalidClawbackDestination,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl<'info> HolderOperations<'info> {
+    pub fn freeze_holder(&mut self) -> Result<()> {
+        freeze_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            FreezeAccount {
+                account: self.token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            &[&[INIT_CONFIG_SEED, &[self.config.bump]]],
+        ))?;
+
+        emit!(HolderFrozen {
+            mint: self.mint.key(),
+            token_account: self.token_account.key(),
+            holder: self.token_account.owner,
+            authority: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Frozen token account: {}", self.token_account.key());
+        Ok(())
+    }
+
+    pub fn thaw_holder(&mut self) -> Result<()> {
+        thaw_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            ThawAccount {
+                account: self.token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            &[&[INIT_CONFIG_SEED, &[self.config.bump]]],
+        ))?;
+
+        emit!(HolderThawed {
+            mint: self.mint.key(),
+            token_account: self.token_account.key(),
+            holder: self.token_account.owner,
+            authority: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Thawed token account: {}", self.token_account.key());
+        Ok(())
+    }
+}
+
+impl<'info> Clawback<'info> {
+    pub fn clawback(&mut self, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[INIT_CONFIG_SEED, &[self.config.bump]]];
+
+        // Frozen accounts cannot be burned from, so thaw for the burn and freeze again after
+        let was_frozen = self.source_token.is_frozen();
+        if was_frozen {
+            thaw_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                ThawAccount {
+                    account: self.source_token.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.source_token.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        if was_frozen {
+            freeze_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                FreezeAccount {
+                    account: self.source_token.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.destination_token.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(ClawedBack {
+            mint: self.mint.key(),
+            source: self.source_token.key(),
+            destination: self.destination_token.key(),
+            holder: self.source_token.owner,
+            amount,
+            authority: self.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Clawed back {} from {} to {}",
+            amount,
+            self.source_token.key(),
+            self.destination_token.key()
+        );
+        Ok(())
+    }
+}
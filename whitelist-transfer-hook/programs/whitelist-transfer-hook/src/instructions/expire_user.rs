@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, WHITELISTED_USER_SEED},
+    error::ErrorCode,
+    events::EntryExpiringSoon,
+    state::{Config, WhitelistedUser},
+};
+
+/// Deactivates an `Expiring` mode entry once its expiry has passed. Queued as a TukTuk task by
+/// `add_to_whitelist` and `schedule_expiry`, so anyone can run it: it only ever deactivates
+/// entries whose time is up.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ExpireUser<'info> {
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [WHITELISTED_USER_SEED, user.as_ref()],
+        bump
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+}
+
+/// Emits `EntryExpiringSoon` for an entry within `config.expiry_notice` of its expiry. Queued
+/// as a TukTuk task next to `expire_user`, and likewise callable by anyone.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct NotifyExpiring<'info> {
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [WHITELISTED_USER_SEED, user.as_ref()],
+        bump = whitelisted_user.bump,
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+}
+
+impl<'info> ExpireUser<'info> {
+    pub fn expire_user(&mut self, user: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now >= self.whitelisted_user.expiry_timestamp,
+            ErrorCode::NotExpired
+        );
+
+        self.whitelisted_user.is_active = false;
+        msg!("Expired user. User: {}", user);
+        Ok(())
+    }
+}
+
+impl<'info> NotifyExpiring<'info> {
+    pub fn notify_expiring(&mut self, user: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let entry = &self.whitelisted_user;
+
+        // A renewed entry is no longer expiring soon, so the old notice task fails here
+        require!(
+            entry.is_valid_at(now)
+                && now >= entry.expiry_timestamp.saturating_sub(self.config.expiry_notice),
+            ErrorCode::NotExpiringSoon
+        );
+
+        emit!(EntryExpiringSoon {
+            user,
+            expiry_timestamp: entry.expiry_timestamp,
+            grace_ends_at: entry
+                .expiry_timestamp
+                .saturating_add(self.config.grace_period),
+            timestamp: now,
+        });
+        msg!(
+            "Entry expiring soon. User: {}, expiry: {}",
+            user,
+            entry.expiry_timestamp
+        );
+        Ok(())
+    }
+}
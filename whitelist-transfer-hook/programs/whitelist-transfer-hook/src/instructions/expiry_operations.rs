@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, QUEUE_AUTHORITY_SEED, WHITELISTED_USER_SEED},
+    error::ErrorCode,
+    events::WhitelistRenewed,
+    instructions::schedule_expiry::ExpiryTasks,
+    state::{Config, EntryExpiry, WhitelistedUser},
+    tuktuk,
+};
+
+#[derive(Accounts)]
+pub struct SetMaxEntryDuration<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetExpiryGrace<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetRetention<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RenewWhitelist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [WHITELISTED_USER_SEED, user.as_ref()],
+        bump = whitelisted_user.bump,
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+    pub system_program: Program<'info, System>,
+    // TukTuk accounts for the renewed expiry's tasks, required once `config.task_queue` is set
+    /// CHECK: must be `config.task_queue`; TukTuk checks the rest.
+    #[account(mut)]
+    pub task_queue: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked by TukTuk.
+    pub task_queue_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: initialized by TukTuk.
+    #[account(mut)]
+    pub task: Option<UncheckedAccount<'info>>,
+    /// CHECK: initialized by TukTuk. Required while `config.expiry_notice` is set.
+    #[account(mut)]
+    pub notice_task: Option<UncheckedAccount<'info>>,
+    /// CHECK: via seeds; signs the `queue_task_v0` CPI.
+    #[account(seeds = [QUEUE_AUTHORITY_SEED], bump)]
+    pub queue_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: TukTuk program.
+    #[account(address = tuktuk::ID)]
+    pub tuktuk_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> SetMaxEntryDuration<'info> {
+    /// Applies to later adds and renewals; existing expiries are left as they are.
+    pub fn set_max_entry_duration(&mut self, max_entry_duration: i64) -> Result<()> {
+        require!(max_entry_duration > 0, ErrorCode::InvalidExpiry);
+        self.config.max_entry_duration = max_entry_duration;
+
+        msg!("Max entry duration: {}s", max_entry_duration);
+        Ok(())
+    }
+}
+
+impl<'info> SetExpiryGrace<'info> {
+    /// Grace period and expiry notice, in seconds; 0 turns either off. The grace period
+    /// applies to every entry at once; the notice only to tasks queued from now on.
+    pub fn set_expiry_grace(&mut self, grace_period: i64, expiry_notice: i64) -> Result<()> {
+        require!(
+            grace_period >= 0 && expiry_notice >= 0,
+            ErrorCode::InvalidExpiry
+        );
+        self.config.grace_period = grace_period;
+        self.config.expiry_notice = expiry_notice;
+
+        msg!(
+            "Grace period: {}s, expiry notice: {}s",
+            grace_period,
+            expiry_notice
+        );
+        Ok(())
+    }
+}
+
+impl<'info> SetRetention<'info> {
+    /// The bounty is capped at an entry's rent, so reaping never costs the rent payer more
+    /// than they paid.
+    pub fn set_retention(&mut self, retention_period: i64, reap_bounty: u64) -> Result<()> {
+        require!(retention_period >= 0, ErrorCode::InvalidExpiry);
+        require!(
+            reap_bounty <= Rent::get()?.minimum_balance(WhitelistedUser::LEN),
+            ErrorCode::InvalidReapBounty
+        );
+        self.config.retention_period = retention_period;
+        self.config.reap_bounty = reap_bounty;
+
+        msg!(
+            "Retention period: {}s, reap bounty: {}",
+            retention_period,
+            reap_bounty
+        );
+        Ok(())
+    }
+}
+
+impl<'info> RenewWhitelist<'info> {
+    /// Moves the expiry later and reactivates an expired entry. An `expire_user` task queued
+    /// for the old expiry fails with `NotExpired` when it runs, so it cannot deactivate the
+    /// renewed entry; once `config.task_queue` is set, new tasks are queued for the new expiry
+    /// as `task_id` (and `notice_task_id`), which must not be in use on the queue.
+    pub fn renew_whitelist(
+        &mut self,
+        bumps: RenewWhitelistBumps,
+        user: Pubkey,
+        expiry: EntryExpiry,
+        task_id: Option<u16>,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let expiry_timestamp = self.config.requested_expiry(now, expiry)?;
+        require!(
+            expiry_timestamp > self.whitelisted_user.expiry_timestamp,
+            ErrorCode::InvalidExpiry
+        );
+
+        self.whitelisted_user.expiry_timestamp = expiry_timestamp;
+        self.whitelisted_user.is_active = true;
+
+        if self.config.task_queue.is_some() {
+            let (Some(task), Some(task_id)) = (&self.task, task_id) else {
+                return err!(ErrorCode::MissingExpiryTask);
+            };
+            ExpiryTasks::from_optional_accounts(
+                &self.config,
+                self.authority.to_account_info(),
+                self.system_program.to_account_info(),
+                &self.task_queue,
+                &self.task_queue_authority,
+                &self.queue_authority,
+                &self.tuktuk_program,
+                bumps.queue_authority,
+            )?
+            .schedule(
+                &self.config,
+                &self.whitelisted_user,
+                task.to_account_info(),
+                task_id,
+                self.notice_task
+                    .as_ref()
+                    .zip(notice_task_id)
+                    .map(|(task, id)| (task.to_account_info(), id)),
+            )?;
+        }
+
+        emit!(WhitelistRenewed {
+            user,
+            expiry_timestamp,
+            renewed_by: self.authority.key(),
+            timestamp: now,
+        });
+        msg!(
+            "Renewed whitelist. User: {}, expiry: {}",
+            user,
+            expiry_timestamp
+        );
+        Ok(())
+    }
+}
@@ -1,17 +1,31 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
 
-use crate::{constant::INIT_CONFIG_SEED, state::Config};
+use crate::{
+    constant::INIT_CONFIG_SEED,
+    error::ErrorCode,
+    state::{Config, WhitelistMode},
+};
 
+/// Only the program's upgrade authority can create the Config, so nobody can front-run the
+/// deployment and take ownership.
 #[derive(Accounts)]
 pub struct InitConfig<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(owner.key())
+            @ ErrorCode::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
 
     #[account(
         init,
-        payer = admin,
+        payer = owner,
         space = Config::LEN,
-        seeds = [INIT_CONFIG_SEED,admin.key().as_ref()],
+        seeds = [INIT_CONFIG_SEED],
         bump,
     )]
     pub config: Account<'info, Config>,
@@ -19,13 +33,35 @@ pub struct InitConfig<'info> {
 }
 
 impl<'info> InitConfig<'info> {
-    pub fn init_config(&mut self, bump: InitConfigBumps) -> Result<()> {
+    pub fn init_config(&mut self, bump: InitConfigBumps, mode: WhitelistMode) -> Result<()> {
         self.config.set_inner(Config {
-            admin: self.admin.key(),
+            owner: self.owner.key(),
+            pending_owner: None,
+            operators: vec![],
+            merkle_root: None,
+            merkle_tier: 0,
+            tier_rules: Config::ALLOW_ALL_TIERS,
+            kyc_provider: None,
+            approved_programs: vec![],
+            paused: false,
+            treasuries: vec![],
+            logged_mints: vec![],
+            trading_schedule: None,
+            max_entry_duration: Config::DEFAULT_MAX_ENTRY_DURATION,
+            task_queue: None,
+            grace_period: 0,
+            expiry_notice: 0,
+            retention_period: Config::DEFAULT_RETENTION_PERIOD,
+            reap_bounty: Config::DEFAULT_REAP_BOUNTY,
+            mode,
             bump: bump.config,
         });
 
-        msg!("Initialized config. Admin: {} ", self.admin.key());
+        msg!(
+            "Initialized config. Owner: {}, mode: {:?}",
+            self.owner.key(),
+            mode
+        );
         Ok(())
     }
 }
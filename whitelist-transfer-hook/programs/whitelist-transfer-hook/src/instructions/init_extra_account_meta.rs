@@ -1,6 +1,11 @@
-use crate::constant::{EXTRA_ACCOUNT_METAS_SEED, WHITELISTED_USER_SEED};
+use crate::constant::{
+    EXTRA_ACCOUNT_METAS_SEED, HOOK_VAULT_ID, INIT_CONFIG_SEED, MERKLE_CLAIM_SEED,
+    TRADING_SCHEDULE_SEED, TRANSFER_LOG_SEED, VAULT_POSITION_SEED, VAULT_SEED,
+    VAULT_WHITELIST_SEED, WHITELISTED_USER_SEED,
+};
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_lang::system_program::{create_account, CreateAccount};
 use anchor_spl::token_interface::Mint;
 use spl_tlv_account_resolution::{
@@ -71,11 +76,19 @@ impl<'info> InitializeExtraAccountMetaList<'info> {
         Ok(())
     }
 
+    /// Layout for a mint that is not logged, which is how every list starts.
+    pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+        Self::extra_account_metas_with_log(false)
+    }
+
     /// Now we need to include the whitelist_entry PDA
     /// But we need to derive it based on the source token owner at runtime
     /// So we use ExtraAccountMeta::new_with_seeds() to tell Token-2022
     /// "derive the PDA using these seeds + the owner pubkey"
-    pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    ///
+    /// `log_writable` marks the mint's TransferLog writable. `set_transfer_log` switches it
+    /// with logging, so transfers of a mint that is not logged do not write-lock its log.
+    pub fn extra_account_metas_with_log(log_writable: bool) -> Result<Vec<ExtraAccountMeta>> {
         Ok(vec![
             // This tells Token-2022: "Derive a PDA with seeds [b"whitelisted_user", source_owner]"
             // The source_owner comes from the transfer instruction
@@ -92,6 +105,136 @@ impl<'info> InitializeExtraAccountMetaList<'info> {
                 false,
             )
             .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Config holds the Merkle root
+            ExtraAccountMeta::new_with_seeds(
+                &[Seed::Literal {
+                    bytes: INIT_CONFIG_SEED.to_vec(),
+                }],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Lets the hook read a `prove_membership` instruction from the same transaction
+            ExtraAccountMeta::new_with_pubkey(&sysvar::instructions::ID.to_bytes().into(), false, false)
+                .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Destination owner's entry, for the tier rules: the owner is bytes 32..64 of the
+            // destination token account (index 2)
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: WHITELISTED_USER_SEED.to_vec(),
+                    },
+                    Seed::AccountData {
+                        account_index: 2,
+                        data_index: 32,
+                        length: 32,
+                    },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Legacy vault whitelist and the mint's (index 1) vault, for `VaultGated` mode
+            ExtraAccountMeta::new_with_seeds(
+                &[Seed::Literal {
+                    bytes: VAULT_WHITELIST_SEED.to_vec(),
+                }],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: VAULT_SEED.to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 },
+                    Seed::Literal {
+                        bytes: HOOK_VAULT_ID.to_le_bytes().to_vec(),
+                    },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // The mint's TransferLog, writable while the hook appends to it
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: TRANSFER_LOG_SEED.to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 },
+                ],
+                false,
+                log_writable,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Market hours
+            ExtraAccountMeta::new_with_seeds(
+                &[Seed::Literal {
+                    bytes: TRADING_SCHEDULE_SEED.to_vec(),
+                }],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Vault positions (in the vault at index 10) of the source owner (index 3) and the
+            // destination owner, for `VaultGated` mode
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: VAULT_POSITION_SEED.to_vec(),
+                    },
+                    Seed::AccountKey { index: 10 },
+                    Seed::AccountKey { index: 3 },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: VAULT_POSITION_SEED.to_vec(),
+                    },
+                    Seed::AccountKey { index: 10 },
+                    Seed::AccountData {
+                        account_index: 2,
+                        data_index: 32,
+                        length: 32,
+                    },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            // Merkle claims of the source owner and the destination owner
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: MERKLE_CLAIM_SEED.to_vec(),
+                    },
+                    Seed::AccountKey { index: 3 },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: MERKLE_CLAIM_SEED.to_vec(),
+                    },
+                    Seed::AccountData {
+                        account_index: 2,
+                        data_index: 32,
+                        length: 32,
+                    },
+                ],
+                false,
+                false,
+            )
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
         ])
     }
 }
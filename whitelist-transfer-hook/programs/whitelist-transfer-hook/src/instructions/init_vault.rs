@@ -1,31 +1,46 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::Vault;
+use crate::{
+    constant::{INIT_CONFIG_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED, VAULT_TOKEN_SEED},
+    error::ErrorCode,
+    state::{Config, Vault},
+};
 
+/// The admin must be the Config owner or the mint authority, so no one else can claim a mint's
+/// vault id (in particular the `HOOK_VAULT_ID` vault the hook gates transfers with).
 #[derive(Accounts)]
+#[instruction(vault_id: u16)]
 pub struct InitializeVault<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.owner == admin.key()
+            || mint.mint_authority == Some(admin.key()).into() @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
 
     pub mint: InterfaceAccount<'info, Mint>,
     #[account(
       init,
       payer=admin,
       space= 8 + Vault::INIT_SPACE,
-      seeds = [b"vault"],
+      seeds = [VAULT_SEED, mint.key().as_ref(), &vault_id.to_le_bytes()],
       bump,
     )]
     pub vault: Account<'info, Vault>,
+    /// CHECK: PDA owning the vault token account
     #[account(
-      seeds = [b"vault_authority"],
+      seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
       bump,
     )]
     pub vault_authority: UncheckedAccount<'info>,
     #[account(
         init,
         payer = admin,
-        seeds = [b"vault-token"],
+        seeds = [VAULT_TOKEN_SEED, vault.key().as_ref()],
         bump,
         token::mint = mint,
         token::authority = vault_authority,
@@ -37,11 +52,14 @@ pub struct InitializeVault<'info> {
 }
 
 impl<'info> InitializeVault<'info> {
-    pub fn initialize_vault(&mut self, bumps: &InitializeVaultBumps) -> Result<()> {
+    pub fn initialize_vault(&mut self, bumps: &InitializeVaultBumps, vault_id: u16) -> Result<()> {
         self.vault.set_inner(Vault {
             admin: self.admin.key(),
             mint: self.mint.key(),
             token_account: self.token_account.key(),
+            vault_id,
+            total_assets: 0,
+            total_shares: 0,
             bump: bumps.vault,
         });
 
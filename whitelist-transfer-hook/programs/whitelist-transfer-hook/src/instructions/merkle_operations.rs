@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, MERKLE_CLAIM_SEED},
+    error::ErrorCode,
+    events::UserWhitelisted,
+    state::{Config, KycInfo, MerkleClaim},
+};
+
+#[derive(Accounts)]
+pub struct SetMerkleRoot<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// The user pays for their own claim, so whitelisting from a root costs the admin nothing.
+#[derive(Accounts)]
+pub struct ClaimWhitelist<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    // A claim under an earlier root is renewed in place
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = MerkleClaim::LEN,
+        seeds = [MERKLE_CLAIM_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub merkle_claim: Account<'info, MerkleClaim>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Placed in the same transaction as a transfer; the hook finds it through the instructions sysvar.
+#[derive(Accounts)]
+pub struct ProveMembership<'info> {
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetMerkleRoot<'info> {
+    /// Every address under `merkle_root` gets `merkle_tier`, whether it claims an entry or
+    /// proves membership in the transfer transaction.
+    pub fn set_merkle_root(
+        &mut self,
+        merkle_root: Option<[u8; 32]>,
+        merkle_tier: u8,
+    ) -> Result<()> {
+        require!(
+            (merkle_tier as usize) < Config::MAX_TIERS,
+            ErrorCode::InvalidKycTier
+        );
+        self.config.merkle_root = merkle_root;
+        self.config.merkle_tier = merkle_tier;
+
+        msg!(
+            "Merkle root updated: {:?}, tier {}",
+            merkle_root,
+            merkle_tier
+        );
+        Ok(())
+    }
+}
+
+impl<'info> ClaimWhitelist<'info> {
+    pub fn claim_whitelist(&mut self, bump: ClaimWhitelistBumps, proof: &[[u8; 32]]) -> Result<()> {
+        let user = self.user.key();
+        self.config.verify_membership(&user, proof)?;
+        let root = self
+            .config
+            .merkle_root
+            .ok_or(error!(ErrorCode::MerkleRootNotSet))?;
+
+        self.merkle_claim.set_inner(MerkleClaim {
+            user,
+            root,
+            bump: bump.merkle_claim,
+        });
+
+        // The root the user was proven against is the attestation for the claim
+        emit!(UserWhitelisted {
+            user,
+            kyc: KycInfo {
+                tier: self.config.merkle_tier,
+                country: [0, 0],
+                attestation: root,
+            },
+            added_by: user,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Whitelist claimed. User: {}", user);
+        Ok(())
+    }
+}
+
+impl<'info> ProveMembership<'info> {
+    pub fn prove_membership(&self, user: Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        self.config.verify_membership(&user, proof)?;
+
+        msg!("Membership proven. User: {}", user);
+        Ok(())
+    }
+}
@@ -2,28 +2,113 @@
 
 use anchor_lang::{
     prelude::*,
-    solana_program::program::invoke,
-    system_program::{create_account, CreateAccount},
+    solana_program::program::{invoke, invoke_signed},
+    system_program::{create_account, transfer, CreateAccount, Transfer},
 };
-use anchor_spl::token_2022::{
-    spl_token_2022::{
-        extension::{transfer_hook::instruction::initialize as init_transfer_hook, ExtensionType},
-        instruction::initialize_mint2,
-        state::Mint as Token2022Mint,
+use anchor_spl::{
+    token_2022::{
+        mint_to,
+        spl_token_2022::{
+            extension::{
+                default_account_state::instruction::initialize_default_account_state,
+                metadata_pointer::instruction::initialize as init_metadata_pointer,
+                transfer_fee::instruction::initialize_transfer_fee_config,
+                transfer_hook::instruction::initialize as init_transfer_hook, ExtensionType,
+            },
+            instruction::{initialize_mint2, initialize_permanent_delegate},
+            state::{AccountState, Mint as Token2022Mint},
+        },
+        MintTo, Token2022,
     },
-    Token2022,
+    token_2022_extensions::{
+        spl_token_metadata_interface::{
+            instruction::{initialize as init_token_metadata, update_field},
+            state::{Field, TokenMetadata},
+        },
+        transfer_fee::{withdraw_withheld_tokens_from_mint, WithdrawWithheldTokensFromMint},
+    },
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, MINT_TOKEN_SEED},
+    error::ErrorCode,
+    events::{FeesWithdrawn, TokensIssued},
+    state::Config,
 };
 
-use crate::{constant::{INIT_CONFIG_SEED, MINT_TOKEN_SEED}, state::Config};
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// TokenMetadata field set by `update_token_metadata`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    /// Additional key-value pair, added if missing.
+    Key(String),
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+            MetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+/// Optional extensions for the mint, on top of the TransferHook and PermanentDelegate
+/// extensions it always gets.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct MintExtensions {
+    /// Enables MetadataPointer (pointing at the mint itself) and TokenMetadata.
+    pub metadata: Option<TokenMetadataArgs>,
+    /// New token accounts start frozen until an admin calls `thaw_holder`.
+    pub default_frozen: bool,
+    pub transfer_fee: Option<TransferFeeArgs>,
+}
+
+impl MintExtensions {
+    /// Fixed-size extensions; TokenMetadata is variable length and allocated by its own instruction.
+    fn extension_types(&self) -> Vec<ExtensionType> {
+        let mut extension_types =
+            vec![ExtensionType::TransferHook, ExtensionType::PermanentDelegate];
+        if self.metadata.is_some() {
+            extension_types.push(ExtensionType::MetadataPointer);
+        }
+        if self.default_frozen {
+            extension_types.push(ExtensionType::DefaultAccountState);
+        }
+        if self.transfer_fee.is_some() {
+            extension_types.push(ExtensionType::TransferFeeConfig);
+        }
+        extension_types
+    }
+}
 
 #[derive(Accounts)]
 pub struct TokenFactory<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
 
     #[account(
-        seeds = [INIT_CONFIG_SEED ,config.admin.key().as_ref()],
+        seeds = [INIT_CONFIG_SEED],
         bump = config.bump,
+        has_one = owner @ crate::error::ErrorCode::Unauthorized,
     )]
     pub config: Account<'info, Config>,
 
@@ -40,96 +125,303 @@ pub struct TokenFactory<'info> {
 }
 
 impl<'info> TokenFactory<'info> {
-    pub fn init_mint(&mut self,bump:TokenFactoryBumps, decimals: u8) -> Result<()> {
-        // Calculate the space needed for mint with TransferHook extension
-        let extension_types = vec![ExtensionType::TransferHook];
+    pub fn init_mint(
+        &mut self,
+        bump: TokenFactoryBumps,
+        decimals: u8,
+        extensions: MintExtensions,
+    ) -> Result<()> {
+        let mint = self.mint.key();
+        let token_program = self.token_program.key();
+
+        // Calculate the space needed for the mint with its fixed-size extensions
+        let extension_types = extensions.extension_types();
         let space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&extension_types)
-            .map_err(|_| error!(crate::error::ErrorCode::ExtensionInitializationFailed))?;
+            .map_err(|_| error!(ErrorCode::ExtensionInitializationFailed))?;
 
-        msg!("Mint account space needed: {} bytes", space);
+        // TokenMetadata reallocates the mint when it is initialized, so fund that space up front
+        let token_metadata = extensions.metadata.as_ref().map(|metadata| TokenMetadata {
+            mint,
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            ..Default::default()
+        });
+        let metadata_space = match &token_metadata {
+            Some(token_metadata) => token_metadata.tlv_size_of()?,
+            None => 0,
+        };
 
-        // Calculate rent
-        let lamports = Rent::get()?.minimum_balance(space);
+        msg!("Mint account space needed: {} bytes (+{} for metadata)", space, metadata_space);
 
+        // Calculate rent
+        let lamports = Rent::get()?.minimum_balance(space + metadata_space);
 
-        let config_binding = self.config.clone().key();
-        let signer_seeds: &[&[u8]] = &[
-            MINT_TOKEN_SEED,
-            config_binding.as_ref(),
-            &[bump.mint],
-        ];
+        // The Config PDA holds every authority of the mint, so only this program's instructions
+        // (and through them the Config roles) can mint, freeze, claw back or change the hook
+        let config = self.config.key();
+        let config_seeds: &[&[u8]] = &[INIT_CONFIG_SEED, &[self.config.bump]];
+        let signer_seeds: &[&[u8]] = &[MINT_TOKEN_SEED, config.as_ref(), &[bump.mint]];
 
         create_account(
             CpiContext::new_with_signer(
                 self.system_program.to_account_info(),
                 CreateAccount {
-                    from: self.payer.to_account_info(),
+                    from: self.owner.to_account_info(),
                     to: self.mint.to_account_info(),
                 },
                 &[signer_seeds],
             ),
             lamports,
             space as u64,
-            &self.token_program.key(),
+            &token_program,
         )?;
 
         msg!("Mint account created");
-        
 
-        // Initialize the TransferHook extension via CPI
-        let init_hook_ix =
-        init_transfer_hook(
-                &self.token_program.key(),
-                &self.mint.key(),
-                Some(self.payer.key()),
-                Some(crate::ID),
-            )?;
+        // Extensions must be initialized before the base mint
+        let mint_info = [self.mint.to_account_info()];
 
         invoke(
-            &init_hook_ix,
-            &[self.mint.to_account_info()],
+            &init_transfer_hook(&token_program, &mint, Some(config), Some(crate::ID))?,
+            &mint_info,
         )?;
-
         msg!("Transfer hook extension initialized");
 
-        // Initialize the base mint via CPI
-        let init_mint_ix = initialize_mint2(
-            &self.token_program.key(),
-            &self.mint.key(),
-            &self.payer.key(),
-            Some(&self.payer.key()),
-            decimals,
-        )?;
+        if extensions.metadata.is_some() {
+            invoke(
+                &init_metadata_pointer(&token_program, &mint, Some(config), Some(mint))?,
+                &mint_info,
+            )?;
+            msg!("Metadata pointer extension initialized");
+        }
 
         invoke(
-            &init_mint_ix,
-            &[self.mint.to_account_info()],
+            &initialize_permanent_delegate(&token_program, &mint, &config)?,
+            &mint_info,
         )?;
+        msg!("Permanent delegate extension initialized: {}", config);
+
+        if extensions.default_frozen {
+            invoke(
+                &initialize_default_account_state(&token_program, &mint, &AccountState::Frozen)?,
+                &mint_info,
+            )?;
+            msg!("Default account state extension initialized: Frozen");
+        }
+
+        if let Some(transfer_fee) = &extensions.transfer_fee {
+            invoke(
+                &initialize_transfer_fee_config(
+                    &token_program,
+                    &mint,
+                    Some(&config),
+                    Some(&config),
+                    transfer_fee.basis_points,
+                    transfer_fee.maximum_fee,
+                )?,
+                &mint_info,
+            )?;
+            msg!("Transfer fee extension initialized");
+        }
 
+        // Initialize the base mint via CPI
+        invoke(
+            &initialize_mint2(&token_program, &mint, &config, Some(&config), decimals)?,
+            &mint_info,
+        )?;
         msg!("Mint initialized successfully");
-        msg!("Mint address: {}", self.mint.key());
+
+        // TokenMetadata needs an initialized mint and the mint authority's signature. The Config
+        // PDA is also update authority, so the metadata follows ownership transfers and only
+        // changes through `update_token_metadata`
+        if let Some(token_metadata) = token_metadata {
+            invoke_signed(
+                &init_token_metadata(
+                    &token_program,
+                    &mint,
+                    &config,
+                    &mint,
+                    &config,
+                    token_metadata.name,
+                    token_metadata.symbol,
+                    token_metadata.uri,
+                ),
+                &[self.mint.to_account_info(), self.config.to_account_info()],
+                &[config_seeds],
+            )?;
+            msg!("Token metadata initialized");
+        }
+
+        msg!("Mint address: {}", mint);
         msg!("Transfer hook program: {}", crate::ID);
-        msg!("Transfer hook authority: {}", self.payer.key());
+        msg!("Transfer hook authority: {}", config);
 
         Ok(())
     }
 }
 
+/// The Config PDA is the mint authority, so new tokens are only issued by the owner through
+/// this instruction.
+#[derive(Accounts)]
+pub struct IssueTokens<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [MINT_TOKEN_SEED, config.key().as_ref()],
+        bump,
+        mint::authority = config,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
 
+/// The Config PDA is the TokenMetadata update authority; the owner changes the metadata here and
+/// pays for any extra rent.
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [MINT_TOKEN_SEED, config.key().as_ref()],
+        bump,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
 
-/*
+/// Transfer fees withheld in token accounts are first moved to the mint with Token 2022's
+/// permissionless `harvest_withheld_tokens_to_mint`; the Config PDA, as withdraw authority,
+/// then pays them out here.
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [MINT_TOKEN_SEED, config.key().as_ref()],
+        bump,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
 
-/// Use when, We need raw solana type .
- pub mint: AccountInfo<'info>, 
+impl<'info> IssueTokens<'info> {
+    pub fn issue_tokens(&mut self, amount: u64) -> Result<()> {
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.destination_token.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                &[&[INIT_CONFIG_SEED, &[self.config.bump]]],
+            ),
+            amount,
+        )?;
+
+        emit!(TokensIssued {
+            mint: self.mint.key(),
+            destination: self.destination_token.key(),
+            amount,
+            authority: self.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Issued {} to {}", amount, self.destination_token.key());
+        Ok(())
+    }
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    pub fn update_token_metadata(&mut self, field: MetadataField, value: String) -> Result<()> {
+        let mint = self.mint.to_account_info();
+        invoke_signed(
+            &update_field(
+                &self.token_program.key(),
+                &mint.key(),
+                &self.config.key(),
+                field.into(),
+                value,
+            ),
+            &[mint.clone(), self.config.to_account_info()],
+            &[&[INIT_CONFIG_SEED, &[self.config.bump]]],
+        )?;
+
+        // Token 2022 resizes the mint to fit the new value; keep it rent exempt
+        let top_up = Rent::get()?
+            .minimum_balance(mint.data_len())
+            .saturating_sub(mint.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.owner.to_account_info(),
+                        to: mint,
+                    },
+                ),
+                top_up,
+            )?;
+        }
 
-/// Anchor has wrapeer on AccountInfo, but at runtime are both same
- pub owner: UncheckedAccount<'info>
+        msg!("Token metadata updated");
+        Ok(())
+    }
+}
 
+impl<'info> WithdrawFees<'info> {
+    pub fn withdraw_fees(&mut self) -> Result<()> {
+        withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            WithdrawWithheldTokensFromMint {
+                token_program_id: self.token_program.to_account_info(),
+                mint: self.mint.to_account_info(),
+                destination: self.destination_token.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            &[&[INIT_CONFIG_SEED, &[self.config.bump]]],
+        ))?;
+
+        emit!(FeesWithdrawn {
+            mint: self.mint.key(),
+            destination: self.destination_token.key(),
+            authority: self.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Withheld fees withdrawn to {}", self.destination_token.key());
+        Ok(())
+    }
+}
 
-**UncheckedAccount**
-This tells Anchor:
- This is an account in instruction
-✔ Validate PDA using seeds
-✔ Do NOT deserialize
-✔ Do NOT expect discriminator
-*/
\ No newline at end of file
@@ -1,15 +1,53 @@
 pub mod init_extra_account_meta;
+pub mod update_extra_account_meta;
 pub mod transfer_hook;
 // pub mod initialize_whitelist;
 pub mod init_config;
 pub mod mint_token;
 // pub mod remove_user;
 pub mod whitelist_operations;
+pub mod role_operations;
+pub mod batch_operations;
+pub mod merkle_operations;
+pub mod compliance_operations;
+pub mod tier_operations;
+pub mod attestation_operations;
+pub mod program_operations;
+pub mod pause_operations;
+pub mod expire_user;
+pub mod reap_expired;
+pub mod expiry_operations;
+pub mod schedule_expiry;
+pub mod vault_whitelist;
+pub mod init_vault;
+pub mod vault_operations;
+pub mod vault_mint;
+pub mod transfer_log_operations;
+pub mod trading_schedule_operations;
 
 pub use init_extra_account_meta::*;
+pub use update_extra_account_meta::*;
 pub use transfer_hook::*;
 // pub use initialize_whitelist::*;
 pub use init_config::*;
 pub use mint_token::*;
 // pub use remove_user::*;
 pub use whitelist_operations::*;
+pub use role_operations::*;
+pub use batch_operations::*;
+pub use merkle_operations::*;
+pub use compliance_operations::*;
+pub use tier_operations::*;
+pub use attestation_operations::*;
+pub use program_operations::*;
+pub use pause_operations::*;
+pub use expire_user::*;
+pub use reap_expired::*;
+pub use expiry_operations::*;
+pub use schedule_expiry::*;
+pub use vault_whitelist::*;
+pub use init_vault::*;
+pub use vault_operations::*;
+pub use vault_mint::*;
+pub use transfer_log_operations::*;
+pub use trading_schedule_operations::*;
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::INIT_CONFIG_SEED,
+    error::ErrorCode,
+    events::{TransfersPaused, TransfersUnpaused},
+    state::Config,
+};
+
+/// Operators can halt transfers too, so a compromise can be stopped without the owner key.
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct TreasuryOperations<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> Pause<'info> {
+    pub fn pause(&mut self) -> Result<()> {
+        self.config.paused = true;
+
+        emit!(TransfersPaused {
+            authority: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Transfers paused by {}", self.authority.key());
+        Ok(())
+    }
+}
+
+impl<'info> Unpause<'info> {
+    pub fn unpause(&mut self) -> Result<()> {
+        self.config.paused = false;
+
+        emit!(TransfersUnpaused {
+            authority: self.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Transfers unpaused");
+        Ok(())
+    }
+}
+
+impl<'info> TreasuryOperations<'info> {
+    pub fn add_treasury(&mut self, token_account: Pubkey) -> Result<()> {
+        require!(
+            !self.config.treasuries.contains(&token_account),
+            ErrorCode::TreasuryAlreadyAdded
+        );
+        require!(
+            self.config.treasuries.len() < Config::MAX_TREASURIES,
+            ErrorCode::TooManyTreasuries
+        );
+        self.config.treasuries.push(token_account);
+
+        msg!("Added treasury: {}", token_account);
+        Ok(())
+    }
+
+    pub fn remove_treasury(&mut self, token_account: Pubkey) -> Result<()> {
+        let pos = self
+            .config
+            .treasuries
+            .iter()
+            .position(|key| *key == token_account)
+            .ok_or(error!(ErrorCode::TreasuryNotFound))?;
+        self.config.treasuries.remove(pos);
+
+        msg!("Removed treasury: {}", token_account);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::INIT_CONFIG_SEED, error::ErrorCode, state::Config};
+
+/// Manages the programs whose PDAs may send tokens without a whitelist entry. See
+/// `TransferHook::is_approved_program_pda` for what an approval lets a program do.
+#[derive(Accounts)]
+pub struct ApprovedProgramOperations<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> ApprovedProgramOperations<'info> {
+    pub fn add_approved_program(&mut self, program_id: Pubkey) -> Result<()> {
+        require!(
+            !self.config.approved_programs.contains(&program_id),
+            ErrorCode::ProgramAlreadyApproved
+        );
+        require!(
+            self.config.approved_programs.len() < Config::MAX_APPROVED_PROGRAMS,
+            ErrorCode::TooManyApprovedPrograms
+        );
+        self.config.approved_programs.push(program_id);
+
+        msg!("Approved program: {}", program_id);
+        Ok(())
+    }
+
+    pub fn remove_approved_program(&mut self, program_id: Pubkey) -> Result<()> {
+        let pos = self
+            .config
+            .approved_programs
+            .iter()
+            .position(|key| *key == program_id)
+            .ok_or(error!(ErrorCode::ProgramNotApproved))?;
+        self.config.approved_programs.remove(pos);
+
+        msg!("Removed approved program: {}", program_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, WHITELISTED_USER_SEED},
+    error::ErrorCode,
+    events::EntryReaped,
+    state::{Config, WhitelistedUser},
+};
+
+/// Closes an entry that has been expired for `config.retention_period`. Anyone can run it: the
+/// rent goes back to the entry's `rent_payer`, less `config.reap_bounty` for the caller.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ReapExpired<'info> {
+    #[account(mut)]
+    pub reaper: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [WHITELISTED_USER_SEED, user.as_ref()],
+        bump = whitelisted_user.bump,
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+    /// CHECK: gets the rent back.
+    #[account(
+        mut,
+        address = whitelisted_user.rent_payer @ ErrorCode::InvalidRentPayer,
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+impl<'info> ReapExpired<'info> {
+    pub fn reap_expired(&mut self, user: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.whitelisted_user.is_reapable_at(
+                now,
+                self.config.grace_period,
+                self.config.retention_period
+            ),
+            ErrorCode::RetentionNotElapsed
+        );
+
+        // The rest of the rent is moved to the rent payer when the entry is closed
+        let bounty = self
+            .config
+            .reap_bounty
+            .min(self.whitelisted_user.get_lamports());
+        self.whitelisted_user.sub_lamports(bounty)?;
+        self.reaper.add_lamports(bounty)?;
+
+        emit!(EntryReaped {
+            user,
+            rent_payer: self.rent_payer.key(),
+            reaped_by: self.reaper.key(),
+            bounty,
+            timestamp: now,
+        });
+        msg!("Reaped expired entry. User: {}, bounty: {}", user, bounty);
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::INIT_CONFIG_SEED, error::ErrorCode, state::Config};
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    pub new_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.pending_owner == Some(new_owner.key()) @ ErrorCode::NotPendingOwner,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct OperatorOperations<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> ProposeOwner<'info> {
+    /// First step of an ownership transfer. Proposing again replaces the pending owner.
+    pub fn propose_owner(&mut self, new_owner: Pubkey) -> Result<()> {
+        self.config.pending_owner = Some(new_owner);
+
+        msg!("Proposed new owner: {}", new_owner);
+        Ok(())
+    }
+}
+
+impl<'info> AcceptOwner<'info> {
+    /// Second step of an ownership transfer, signed by the proposed owner.
+    pub fn accept_owner(&mut self) -> Result<()> {
+        self.config.owner = self.new_owner.key();
+        self.config.pending_owner = None;
+
+        msg!("Ownership accepted. Owner: {}", self.new_owner.key());
+        Ok(())
+    }
+}
+
+impl<'info> OperatorOperations<'info> {
+    pub fn add_operator(&mut self, operator: Pubkey) -> Result<()> {
+        require!(
+            !self.config.operators.contains(&operator),
+            ErrorCode::OperatorAlreadyAdded
+        );
+        require!(
+            self.config.operators.len() < Config::MAX_OPERATORS,
+            ErrorCode::TooManyOperators
+        );
+        self.config.operators.push(operator);
+
+        msg!("Added operator: {}", operator);
+        Ok(())
+    }
+
+    pub fn remove_operator(&mut self, operator: Pubkey) -> Result<()> {
+        let pos = self
+            .config
+            .operators
+            .iter()
+            .position(|key| *key == operator)
+            .ok_or(error!(ErrorCode::OperatorNotFound))?;
+        self.config.operators.remove(pos);
+
+        msg!("Removed operator: {}", operator);
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, QUEUE_AUTHORITY_SEED, WHITELISTED_USER_SEED},
+    error::ErrorCode,
+    state::{Config, WhitelistMode, WhitelistedUser},
+    tuktuk::{self, QueueTaskArgsV0, TransactionSourceV0, TriggerV0},
+};
+
+#[derive(Accounts)]
+pub struct SetTaskQueue<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Queues the `expire_user` task, and the `notify_expiring` one while `config.expiry_notice` is
+/// set, for an existing entry, e.g. one added before `config.task_queue` was set.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ScheduleExpiry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
+        constraint = config.mode == WhitelistMode::Expiring @ ErrorCode::NotExpiringMode,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [WHITELISTED_USER_SEED, user.as_ref()],
+        bump = whitelisted_user.bump,
+    )]
+    pub whitelisted_user: Account<'info, WhitelistedUser>,
+    /// CHECK: must be `config.task_queue`; TukTuk checks the rest.
+    #[account(
+        mut,
+        constraint = config.task_queue == Some(task_queue.key()) @ ErrorCode::WrongTaskQueue,
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+    /// CHECK: checked by TukTuk.
+    pub task_queue_authority: UncheckedAccount<'info>,
+    /// CHECK: initialized by TukTuk.
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
+    /// CHECK: initialized by TukTuk. Required while `config.expiry_notice` is set.
+    #[account(mut)]
+    pub notice_task: Option<UncheckedAccount<'info>>,
+    /// CHECK: via seeds; signs the `queue_task_v0` CPI.
+    #[account(seeds = [QUEUE_AUTHORITY_SEED], bump)]
+    pub queue_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: TukTuk program.
+    #[account(address = tuktuk::ID)]
+    pub tuktuk_program: UncheckedAccount<'info>,
+}
+
+impl<'info> SetTaskQueue<'info> {
+    /// `None` stops scheduling; tasks already queued still run.
+    pub fn set_task_queue(&mut self, task_queue: Option<Pubkey>) -> Result<()> {
+        self.config.task_queue = task_queue;
+
+        msg!("Task queue: {:?}", task_queue);
+        Ok(())
+    }
+}
+
+impl<'info> ScheduleExpiry<'info> {
+    pub fn schedule_expiry(
+        &mut self,
+        bumps: ScheduleExpiryBumps,
+        user: Pubkey,
+        task_id: u16,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        ExpiryTasks {
+            payer: self.authority.to_account_info(),
+            queue_authority: self.queue_authority.to_account_info(),
+            task_queue_authority: self.task_queue_authority.to_account_info(),
+            task_queue: self.task_queue.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            tuktuk_program: self.tuktuk_program.to_account_info(),
+            queue_authority_bump: bumps.queue_authority,
+        }
+        .schedule(
+            &self.config,
+            &self.whitelisted_user,
+            self.task.to_account_info(),
+            task_id,
+            self.notice_task
+                .as_ref()
+                .zip(notice_task_id)
+                .map(|(task, id)| (task.to_account_info(), id)),
+        )?;
+
+        msg!("Scheduled expiry. User: {}", user);
+        Ok(())
+    }
+}
+
+/// TukTuk accounts for queueing an entry's tasks. The payer covers the task accounts' rent
+/// and the `queue_authority` PDA signs.
+pub(crate) struct ExpiryTasks<'info> {
+    pub payer: AccountInfo<'info>,
+    pub queue_authority: AccountInfo<'info>,
+    pub task_queue_authority: AccountInfo<'info>,
+    pub task_queue: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub tuktuk_program: AccountInfo<'info>,
+    pub queue_authority_bump: u8,
+}
+
+impl<'info> ExpiryTasks<'info> {
+    /// From an instruction's optional TukTuk accounts, for instructions that only queue tasks
+    /// once `config.task_queue` is set. Fails with `MissingExpiryTask` unless all are passed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_optional_accounts(
+        config: &Config,
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        task_queue: &Option<UncheckedAccount<'info>>,
+        task_queue_authority: &Option<UncheckedAccount<'info>>,
+        queue_authority: &Option<UncheckedAccount<'info>>,
+        tuktuk_program: &Option<UncheckedAccount<'info>>,
+        queue_authority_bump: Option<u8>,
+    ) -> Result<Self> {
+        let (
+            Some(task_queue),
+            Some(task_queue_authority),
+            Some(queue_authority),
+            Some(tuktuk_program),
+            Some(queue_authority_bump),
+        ) = (
+            task_queue,
+            task_queue_authority,
+            queue_authority,
+            tuktuk_program,
+            queue_authority_bump,
+        )
+        else {
+            return err!(ErrorCode::MissingExpiryTask);
+        };
+        require!(
+            config.task_queue == Some(task_queue.key()),
+            ErrorCode::WrongTaskQueue
+        );
+
+        Ok(Self {
+            payer,
+            queue_authority: queue_authority.to_account_info(),
+            task_queue_authority: task_queue_authority.to_account_info(),
+            task_queue: task_queue.to_account_info(),
+            system_program,
+            tuktuk_program: tuktuk_program.to_account_info(),
+            queue_authority_bump,
+        })
+    }
+
+    /// Queues `expire_user` at the entry's expiry as task `task_id` and, while
+    /// `config.expiry_notice` is set, `notify_expiring` that many seconds earlier as
+    /// `notice_task`.
+    pub fn schedule(
+        &self,
+        config: &Account<'info, Config>,
+        whitelisted_user: &Account<'info, WhitelistedUser>,
+        task: AccountInfo<'info>,
+        task_id: u16,
+        notice_task: Option<(AccountInfo<'info>, u16)>,
+    ) -> Result<()> {
+        let expiry_timestamp = whitelisted_user.expiry_timestamp;
+        self.queue(
+            task,
+            task_id,
+            Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::ExpireUser {
+                    config: config.key(),
+                    whitelisted_user: whitelisted_user.key(),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::ExpireUser {
+                    user: whitelisted_user.user,
+                }
+                .data(),
+            },
+            expiry_timestamp,
+            "expire whitelist user",
+        )?;
+
+        if config.expiry_notice > 0 {
+            let (notice_task, notice_task_id) =
+                notice_task.ok_or(error!(ErrorCode::MissingExpiryTask))?;
+            self.queue(
+                notice_task,
+                notice_task_id,
+                Instruction {
+                    program_id: crate::ID,
+                    accounts: crate::accounts::NotifyExpiring {
+                        config: config.key(),
+                        whitelisted_user: whitelisted_user.key(),
+                    }
+                    .to_account_metas(None),
+                    data: crate::instruction::NotifyExpiring {
+                        user: whitelisted_user.user,
+                    }
+                    .data(),
+                },
+                expiry_timestamp.saturating_sub(config.expiry_notice),
+                "warn whitelist user of expiry",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Queues `instruction`, which needs no signer, to run at `timestamp` as task `task_id`.
+    fn queue(
+        &self,
+        task: AccountInfo<'info>,
+        task_id: u16,
+        instruction: Instruction,
+        timestamp: i64,
+        description: &str,
+    ) -> Result<()> {
+        tuktuk::queue_task_v0(
+            self.tuktuk_program.clone(),
+            tuktuk::QueueTaskV0 {
+                payer: self.payer.clone(),
+                queue_authority: self.queue_authority.clone(),
+                task_queue_authority: self.task_queue_authority.clone(),
+                task_queue: self.task_queue.clone(),
+                task,
+                system_program: self.system_program.clone(),
+            },
+            QueueTaskArgsV0 {
+                id: task_id,
+                trigger: TriggerV0::Timestamp(timestamp),
+                transaction: TransactionSourceV0::CompiledV0(tuktuk::compile_transaction(&[
+                    instruction,
+                ])),
+                crank_reward: None,
+                free_tasks: 0,
+                description: description.to_string(),
+            },
+            &[&[QUEUE_AUTHORITY_SEED, &[self.queue_authority_bump]]],
+        )?;
+
+        msg!("Queued {}. Task: {}, at: {}", description, task_id, timestamp);
+        Ok(())
+    }
+}
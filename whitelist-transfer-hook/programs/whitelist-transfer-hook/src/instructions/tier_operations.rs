@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::INIT_CONFIG_SEED, error::ErrorCode, state::Config};
+
+#[derive(Accounts)]
+pub struct SetTierRule<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetTierRule<'info> {
+    /// Bit `n` of `allowed_destination_tiers` lets `source_tier` transfer to tier `n`.
+    pub fn set_tier_rule(&mut self, source_tier: u8, allowed_destination_tiers: u8) -> Result<()> {
+        let rule = self
+            .config
+            .tier_rules
+            .get_mut(source_tier as usize)
+            .ok_or(error!(ErrorCode::InvalidKycTier))?;
+        *rule = allowed_destination_tiers;
+
+        msg!(
+            "Tier {} may transfer to tiers {:#010b}",
+            source_tier,
+            allowed_destination_tiers
+        );
+        Ok(())
+    }
+}
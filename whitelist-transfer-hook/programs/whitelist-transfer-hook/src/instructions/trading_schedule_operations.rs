@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, TRADING_SCHEDULE_SEED},
+    error::ErrorCode,
+    state::{BlackoutPeriod, Config, TradingSchedule, TradingWindow},
+};
+
+#[derive(Accounts)]
+pub struct InitTradingSchedule<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = owner,
+        space = TradingSchedule::LEN,
+        seeds = [TRADING_SCHEDULE_SEED],
+        bump,
+    )]
+    pub trading_schedule: Account<'info, TradingSchedule>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces the windows and blackouts, or turns enforcement on or off.
+#[derive(Accounts)]
+pub struct TradingScheduleOperations<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [TRADING_SCHEDULE_SEED],
+        bump = trading_schedule.bump,
+    )]
+    pub trading_schedule: Account<'info, TradingSchedule>,
+}
+
+impl<'info> InitTradingSchedule<'info> {
+    pub fn init_trading_schedule(
+        &mut self,
+        bumps: InitTradingScheduleBumps,
+        windows: Vec<TradingWindow>,
+        blackouts: Vec<BlackoutPeriod>,
+    ) -> Result<()> {
+        TradingSchedule::validate(&windows, &blackouts)?;
+        self.trading_schedule.set_inner(TradingSchedule {
+            windows,
+            blackouts,
+            bump: bumps.trading_schedule,
+        });
+
+        msg!("Created trading schedule");
+        Ok(())
+    }
+}
+
+impl<'info> TradingScheduleOperations<'info> {
+    pub fn update_trading_schedule(
+        &mut self,
+        windows: Vec<TradingWindow>,
+        blackouts: Vec<BlackoutPeriod>,
+    ) -> Result<()> {
+        TradingSchedule::validate(&windows, &blackouts)?;
+        msg!(
+            "Trading schedule: {} windows, {} blackouts",
+            windows.len(),
+            blackouts.len()
+        );
+        self.trading_schedule.windows = windows;
+        self.trading_schedule.blackouts = blackouts;
+        Ok(())
+    }
+
+    pub fn set_trading_schedule(&mut self, enabled: bool) -> Result<()> {
+        self.config.trading_schedule = enabled.then(|| self.trading_schedule.key());
+
+        msg!("Trading schedule enforced: {}", enabled);
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 use std::cell::RefMut;
 
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+    },
+};
 use anchor_spl::{
     token_2022::spl_token_2022::{
         extension::{
@@ -11,8 +16,22 @@ use anchor_spl::{
     },
     token_interface::{Mint, TokenAccount},
 };
+use solana_curve25519::edwards::{validate_edwards, PodEdwardsPoint};
 
-use crate::{constant::{EXTRA_ACCOUNT_METAS_SEED, WHITELISTED_USER_SEED}, error::ErrorCode, state::WhitelistedUser};
+use crate::{
+    constant::{
+        EXTRA_ACCOUNT_METAS_SEED, HOOK_VAULT_ID, INIT_CONFIG_SEED, MERKLE_CLAIM_SEED,
+        TRADING_SCHEDULE_SEED, TRANSFER_LOG_SEED, VAULT_POSITION_SEED, VAULT_SEED,
+        VAULT_WHITELIST_SEED, WHITELISTED_USER_SEED,
+    },
+    error::ErrorCode,
+    events::TransferLogged,
+    instruction,
+    state::{
+        Config, MerkleClaim, TradingSchedule, TransferLog, TransferLogEntry, Vault, VaultPosition,
+        Whitelist, WhitelistMode, WhitelistedUser,
+    },
+};
 
 #[derive(Accounts)]
 pub struct TransferHook<'info> {
@@ -35,29 +54,310 @@ pub struct TransferHook<'info> {
     )]
     pub extra_account_meta_list: UncheckedAccount<'info>,
     
+    /// CHECK: WhitelistedUser PDA of the source owner. May not exist when the owner proves
+    /// Merkle membership in the same transaction instead.
     #[account(
         seeds = [WHITELISTED_USER_SEED, source_token.owner.key().as_ref()],
-        bump= whitelisted_user.bump,
+        bump,
+    )]
+    pub whitelisted_user: UncheckedAccount<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: Instructions sysvar, used to look for a `prove_membership` instruction and the
+    /// program moving tokens out of a PDA
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    /// CHECK: WhitelistedUser PDA of the destination owner, for the tier rules. May not exist.
+    #[account(
+        seeds = [WHITELISTED_USER_SEED, destination_token.owner.key().as_ref()],
+        bump,
     )]
-    pub whitelisted_user: Account<'info, WhitelistedUser>,
+    pub destination_whitelisted_user: UncheckedAccount<'info>,
+    /// CHECK: Legacy vault whitelist, only read in `VaultGated` mode until it is migrated.
+    /// May not exist.
+    #[account(
+        seeds = [VAULT_WHITELIST_SEED],
+        bump,
+    )]
+    pub vault_whitelist: UncheckedAccount<'info>,
+    /// CHECK: The mint's `HOOK_VAULT_ID` vault, only read in `VaultGated` mode. May not exist.
+    #[account(
+        seeds = [VAULT_SEED, mint.key().as_ref(), &HOOK_VAULT_ID.to_le_bytes()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: TransferLog of the mint, written while the mint is in `config.logged_mints`
+    /// (and only writable then). May not exist.
+    #[account(
+        seeds = [TRANSFER_LOG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub transfer_log: UncheckedAccount<'info>,
+    /// CHECK: TradingSchedule, read while `config.trading_schedule` is set. May not exist.
+    #[account(
+        seeds = [TRADING_SCHEDULE_SEED],
+        bump,
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+    /// CHECK: VaultPosition of the source owner, only read in `VaultGated` mode. May not exist.
+    #[account(
+        seeds = [VAULT_POSITION_SEED, vault.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub source_vault_position: UncheckedAccount<'info>,
+    /// CHECK: VaultPosition of the destination owner, only read in `VaultGated` mode. May not
+    /// exist.
+    #[account(
+        seeds = [
+            VAULT_POSITION_SEED,
+            vault.key().as_ref(),
+            destination_token.owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub destination_vault_position: UncheckedAccount<'info>,
+    /// CHECK: MerkleClaim of the source owner. May not exist.
+    #[account(
+        seeds = [MERKLE_CLAIM_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub source_merkle_claim: UncheckedAccount<'info>,
+    /// CHECK: MerkleClaim of the destination owner, for the tier rules. May not exist.
+    #[account(
+        seeds = [MERKLE_CLAIM_SEED, destination_token.owner.key().as_ref()],
+        bump,
+    )]
+    pub destination_merkle_claim: UncheckedAccount<'info>,
 }
 
 impl<'info> TransferHook<'info> {
-    pub fn transfer_hook(&mut self, _amount: u64) -> Result<()> {
+    pub fn transfer_hook(&mut self, amount: u64) -> Result<()> {
         self.check_is_transferring()?;
 
+        if self.config.paused {
+            require!(
+                self.config
+                    .is_treasury_transfer(&self.source_token.key(), &self.destination_token.key()),
+                ErrorCode::TransfersPaused
+            );
+            msg!("Paused: transfer between treasury accounts");
+        }
+
+        if self.config.trading_schedule.is_some() {
+            // `set_trading_schedule` only enables an existing schedule
+            let schedule = program_account::<TradingSchedule>(&self.trading_schedule)?
+                .ok_or(anchor_lang::error::ErrorCode::AccountNotInitialized)?;
+            schedule.check(Clock::get()?.unix_timestamp)?;
+        }
+
+        match self.config.mode {
+            WhitelistMode::PerUser | WhitelistMode::Expiring => self.check_whitelisted_user()?,
+            WhitelistMode::VaultGated => self.check_vault_transfer()?,
+        }
+
+        if self.config.logged_mints.contains(&self.mint.key()) {
+            self.log_transfer(amount)?;
+        }
+        Ok(())
+    }
+
+    /// Emits the transfer and appends it to the mint's `TransferLog`.
+    fn log_transfer(&self, amount: u64) -> Result<()> {
+        // `set_transfer_log` only enables mints whose log exists, and makes it writable
+        require!(
+            self.transfer_log.is_writable,
+            ErrorCode::TransferLogNotWritable
+        );
+        let mut log = program_account::<TransferLog>(&self.transfer_log)?
+            .ok_or(anchor_lang::error::ErrorCode::AccountNotInitialized)?;
+        let clock = Clock::get()?;
+        let entry = TransferLogEntry {
+            source_owner: self.source_token.owner,
+            destination_owner: self.destination_token.owner,
+            amount,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        };
+        log.append(entry);
+
+        emit!(TransferLogged {
+            mint: self.mint.key(),
+            source_owner: entry.source_owner,
+            destination_owner: entry.destination_owner,
+            amount,
+            slot: entry.slot,
+            timestamp: entry.timestamp,
+        });
+
+        let mut data = self.transfer_log.try_borrow_mut_data()?;
+        log.try_serialize(&mut &mut data[..])
+    }
+
+    /// Source owner has a WhitelistedUser entry, valid or in its grace period (or a Merkle claim
+    /// under the current root, or proves Merkle membership, or is a PDA of an approved program),
+    /// the destination owner has no entry, a valid one or a Merkle claim, and the tier rules
+    /// allow sending to the destination.
+    fn check_whitelisted_user(&self) -> Result<()> {
+        let source_owner = self.source_token.owner;
+        let destination_owner = self.destination_token.owner;
+        msg!("Source token owner: {}", source_owner);
+        msg!("Destination token owner: {}", destination_owner);
+
+        let now = Clock::get()?.unix_timestamp;
+        let source_entry = whitelisted_user(&self.whitelisted_user, &source_owner)?;
+        let source_tier = match source_entry {
+            Some(entry) if entry.is_valid_at(now) => {
+                msg!("Transfer allowed: The address is whitelisted");
+                entry.kyc.tier
+            }
+            Some(entry) if entry.is_in_grace_at(now, self.config.grace_period) => {
+                msg!("Transfer allowed: The entry is in its grace period");
+                entry.kyc.tier
+            }
+            _ if self.has_merkle_claim(&self.source_merkle_claim, &source_owner)? => {
+                msg!("Transfer allowed: Merkle claim under the current root");
+                self.config.merkle_tier
+            }
+            _ if self.has_membership_proof(&source_owner)? => {
+                msg!("Transfer allowed: Merkle membership proven");
+                self.config.merkle_tier
+            }
+            _ if self.is_approved_program_pda()? => {
+                msg!("Transfer allowed: PDA of an approved program");
+                // Like any owner without an entry, the PDA counts as tier 0
+                0
+            }
+            Some(_) => return err!(ErrorCode::WhitelistEntryExpired),
+            None => return err!(ErrorCode::NotWhiteListed),
+        };
+
+        // Expired entries, in their grace period or not, may only wind down their holdings
+        let destination_entry =
+            whitelisted_user(&self.destination_whitelisted_user, &destination_owner)?;
+        let destination_tier = match destination_entry {
+            Some(entry) if entry.is_valid_at(now) => entry.kyc.tier,
+            _ if self.has_merkle_claim(&self.destination_merkle_claim, &destination_owner)? => {
+                self.config.merkle_tier
+            }
+            Some(entry) if entry.is_in_grace_at(now, self.config.grace_period) => {
+                return err!(ErrorCode::EntryInGracePeriod)
+            }
+            Some(_) => return err!(ErrorCode::WhitelistEntryExpired),
+            // A destination that was never listed (or whose entry was reaped) counts as tier 0
+            None => 0,
+        };
+        require!(
+            self.config.can_transfer_between(source_tier, destination_tier),
+            ErrorCode::TierTransferNotAllowed
+        );
+        msg!("Tier {} -> tier {}", source_tier, destination_tier);
+        Ok(())
+    }
+
+    /// Every transfer has to go into or out of the vault, and the user on the other side has
+    /// to be on the vault whitelist.
+    fn check_vault_transfer(&self) -> Result<()> {
+        let vault = program_account::<Vault>(&self.vault)?.ok_or(ErrorCode::TransferMustTouchVault)?;
+        require_keys_eq!(vault.mint, self.mint.key(), ErrorCode::InvalidVaultMint);
+
+        let is_source_vault = self.source_token.key() == vault.token_account;
+        let is_destination_vault = self.destination_token.key() == vault.token_account;
+        require!(
+            is_source_vault || is_destination_vault,
+            ErrorCode::TransferMustTouchVault
+        );
+
         msg!("Source token owner: {}", self.source_token.owner);
-        msg!("Whitelisted User : {}", self.whitelisted_user.user);
         msg!("Destination token owner: {}", self.destination_token.owner);
 
-        if self.whitelisted_user.user == self.source_token.owner {
-            msg!("Transfer allowed: The address is whitelisted");
-        } else {
-            panic!("TransferHook: Address is not whitelisted");
+        // Users not yet moved out of the legacy whitelist are still looked up there
+        let whitelist = program_account::<Whitelist>(&self.vault_whitelist)?;
+        let is_listed = |position: &UncheckedAccount, user: &Pubkey| -> Result<bool> {
+            if program_account::<VaultPosition>(position)?.is_some() {
+                return Ok(true);
+            }
+            Ok(whitelist
+                .as_ref()
+                .is_some_and(|whitelist| whitelist.address.iter().any(|entry| entry.user == *user)))
+        };
+
+        if is_destination_vault {
+            require!(
+                is_listed(&self.source_vault_position, &self.source_token.owner)?,
+                ErrorCode::SourceNotWhitelisted
+            );
+        }
+
+        if is_source_vault {
+            require!(
+                is_listed(&self.destination_vault_position, &self.destination_token.owner)?,
+                ErrorCode::DestinationNotWhitelisted
+            );
         }
+
         Ok(())
     }
 
+    /// `account` holds a MerkleClaim of `user` made under the current root.
+    fn has_merkle_claim(&self, account: &UncheckedAccount, user: &Pubkey) -> Result<bool> {
+        Ok(program_account::<MerkleClaim>(account)?.is_some_and(|claim| {
+            claim.user == *user && Some(claim.root) == self.config.merkle_root
+        }))
+    }
+
+    /// `prove_membership` instruction for the source owner anywhere in this transaction.
+    /// The proof is verified again here, so the hook does not depend on instruction order.
+    fn has_membership_proof(&self, source_owner: &Pubkey) -> Result<bool> {
+        if self.config.merkle_root.is_none() {
+            return Ok(false);
+        }
+
+        let sysvar = self.instructions_sysvar.to_account_info();
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, &sysvar) {
+            index += 1;
+            if ix.program_id != crate::ID
+                || !ix.data.starts_with(instruction::ProveMembership::DISCRIMINATOR)
+            {
+                continue;
+            }
+            let args = instruction::ProveMembership::deserialize(
+                &mut &ix.data[instruction::ProveMembership::DISCRIMINATOR.len()..],
+            )?;
+            if args.user == *source_owner
+                && self.config.verify_membership(source_owner, &args.proof).is_ok()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Source owner is a PDA account owned by an approved program, and that program is the
+    /// top-level instruction moving the tokens.
+    ///
+    /// The hook cannot see the seeds behind a PDA, so it relies on the owner account being owned
+    /// by the approved program, which only that program can arrange, and on only the approved
+    /// program being able to sign for its PDAs within its own instruction. Data-less authority
+    /// PDAs are not accepted, since nothing ties them to a program. Approve only programs that
+    /// do not CPI into arbitrary programs.
+    fn is_approved_program_pda(&self) -> Result<bool> {
+        if !self.config.approved_programs.contains(self.owner.owner)
+            || is_on_curve(&self.owner.key())
+        {
+            return Ok(false);
+        }
+
+        let sysvar = self.instructions_sysvar.to_account_info();
+        let current = load_current_index_checked(&sysvar)?;
+        let program_id = load_instruction_at_checked(current as usize, &sysvar)?.program_id;
+
+        Ok(*self.owner.owner == program_id)
+    }
+
     /// Checks if the transfer hook is being executed during a transfer operation.
     fn check_is_transferring(&mut self) -> Result<()> {
        // Ensure that the source token account has the transfer hook extension enabled
@@ -89,3 +389,24 @@ impl<'info> TransferHook<'info> {
         Ok(())
     }
 }
+
+/// Keypair addresses are points on the Ed25519 curve, PDAs are not.
+fn is_on_curve(key: &Pubkey) -> bool {
+    validate_edwards(&PodEdwardsPoint(key.to_bytes()))
+}
+
+/// WhitelistedUser account created by an admin or by self-registration, if `account` holds one
+/// for `user`.
+fn whitelisted_user(account: &UncheckedAccount, user: &Pubkey) -> Result<Option<WhitelistedUser>> {
+    Ok(program_account::<WhitelistedUser>(account)?.filter(|entry| entry.user == *user))
+}
+
+/// Account of this program held by an optional extra account, or `None` if it was never
+/// created.
+fn program_account<T: AccountDeserialize>(account: &UncheckedAccount) -> Result<Option<T>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::{
+    constant::{EXTRA_ACCOUNT_METAS_SEED, INIT_CONFIG_SEED, TRANSFER_LOG_SEED},
+    error::ErrorCode,
+    instructions::InitializeExtraAccountMetaList,
+    state::{Config, TransferLog},
+};
+
+#[derive(Accounts)]
+pub struct InitTransferLog<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        space = TransferLog::LEN,
+        seeds = [TRANSFER_LOG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub transfer_log: Account<'info, TransferLog>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Turns logging of the log's mint on or off. Turning it off keeps the recorded entries.
+/// The mint's ExtraAccountMetaList is rewritten so that the TransferLog is only writable in
+/// transfers while logging is on; it must already be on the program's current layout.
+#[derive(Accounts)]
+pub struct SetTransferLog<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [TRANSFER_LOG_SEED, transfer_log.mint.as_ref()],
+        bump = transfer_log.bump,
+    )]
+    pub transfer_log: Account<'info, TransferLog>,
+    /// CHECK: ExtraAccountMetaList of the log's mint, owned by this program
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, transfer_log.mint.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+}
+
+impl<'info> InitTransferLog<'info> {
+    pub fn init_transfer_log(&mut self, bumps: InitTransferLogBumps) -> Result<()> {
+        self.transfer_log.set_inner(TransferLog {
+            mint: self.mint.key(),
+            head: 0,
+            total: 0,
+            entries: Vec::with_capacity(TransferLog::CAPACITY),
+            bump: bumps.transfer_log,
+        });
+
+        msg!("Created transfer log for mint {}", self.mint.key());
+        Ok(())
+    }
+}
+
+impl<'info> SetTransferLog<'info> {
+    pub fn set_transfer_log(&mut self, enabled: bool) -> Result<()> {
+        let mint = self.transfer_log.mint;
+        let logged_mints = &mut self.config.logged_mints;
+
+        if enabled && !logged_mints.contains(&mint) {
+            require!(
+                logged_mints.len() < Config::MAX_LOGGED_MINTS,
+                ErrorCode::TooManyLoggedMints
+            );
+            logged_mints.push(mint);
+        } else if !enabled {
+            logged_mints.retain(|key| *key != mint);
+        }
+
+        let extra_account_metas =
+            InitializeExtraAccountMetaList::extra_account_metas_with_log(enabled)?;
+        let mut data = self.extra_account_meta_list.try_borrow_mut_data()?;
+        require_eq!(
+            data.len(),
+            ExtraAccountMetaList::size_of(extra_account_metas.len())
+                .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?,
+            ErrorCode::ExtraAccountMetaError
+        );
+        ExtraAccountMetaList::update::<ExecuteInstruction>(&mut data, &extra_account_metas)
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?;
+
+        msg!("Transfer log for mint {}: {}", mint, enabled);
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::transfer_hook::TransferHook,
+    token_interface::{get_mint_extension_data, Mint},
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::constant::{EXTRA_ACCOUNT_METAS_SEED, INIT_CONFIG_SEED};
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+/// Borsh mirror of `ExtraAccountMeta`. A `Vec` of these has the same wire format as the
+/// interface's `UpdateExtraAccountMetaList` data (u32 length, then 35 bytes per entry).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtraAccountMetaArgs {
+    pub discriminator: u8,
+    pub address_config: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<ExtraAccountMetaArgs> for ExtraAccountMeta {
+    fn from(args: ExtraAccountMetaArgs) -> Self {
+        Self {
+            discriminator: args.discriminator,
+            address_config: args.address_config,
+            is_signer: args.is_signer.into(),
+            is_writable: args.is_writable.into(),
+        }
+    }
+}
+
+impl From<ExtraAccountMeta> for ExtraAccountMetaArgs {
+    fn from(meta: ExtraAccountMeta) -> Self {
+        Self {
+            discriminator: meta.discriminator,
+            address_config: meta.address_config,
+            is_signer: meta.is_signer.into(),
+            is_writable: meta.is_writable.into(),
+        }
+    }
+}
+
+/// Account order follows the transfer hook interface (meta list, mint, authority). The
+/// authority is writable here because it pays for growth and receives the refund on shrink.
+/// On mints whose transfer hook authority is the Config PDA, like the program's own mint, the
+/// Config owner signs as `authority` and passes `config`.
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    /// CHECK: ExtraAccountMetaList Account, must use these seeds
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+    #[account(
+        extensions::transfer_hook::program_id = crate::ID,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Option<Account<'info, Config>>,
+}
+
+impl<'info> UpdateExtraAccountMetaList<'info> {
+    pub fn update_extra_account_meta_list(
+        &mut self,
+        extra_account_metas: Vec<ExtraAccountMetaArgs>,
+    ) -> Result<()> {
+        self.check_authority()?;
+        let extra_account_metas: Vec<ExtraAccountMeta> =
+            extra_account_metas.into_iter().map(Into::into).collect();
+
+        let new_size = ExtraAccountMetaList::size_of(extra_account_metas.len())
+            .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?;
+        let old_size = self.extra_account_meta_list.data_len();
+        let rent_exempt = Rent::get()?.minimum_balance(new_size);
+        let current_lamports = self.extra_account_meta_list.lamports();
+
+        // Grow (and top up rent) before rewriting, since the TLV entry must fit the account
+        if new_size > old_size {
+            if rent_exempt > current_lamports {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.authority.to_account_info(),
+                            to: self.extra_account_meta_list.to_account_info(),
+                        },
+                    ),
+                    rent_exempt - current_lamports,
+                )?;
+            }
+            self.extra_account_meta_list.resize(new_size)?;
+        }
+
+        {
+            let mut data = self.extra_account_meta_list.try_borrow_mut_data()?;
+            ExtraAccountMetaList::update::<ExecuteInstruction>(&mut data, &extra_account_metas)
+                .map_err(|_| error!(ErrorCode::ExtraAccountMetaError))?;
+        }
+
+        // Shrink after rewriting and hand the freed rent back to the authority
+        if new_size < old_size {
+            self.extra_account_meta_list.resize(new_size)?;
+            let excess = current_lamports.saturating_sub(rent_exempt);
+            **self.extra_account_meta_list.try_borrow_mut_lamports()? -= excess;
+            **self.authority.try_borrow_mut_lamports()? += excess;
+        }
+
+        msg!(
+            "ExtraAccountMetaList updated with {} entries",
+            extra_account_metas.len()
+        );
+        Ok(())
+    }
+
+    /// `authority` is the mint's transfer hook authority, or the Config owner when that
+    /// authority is the Config PDA.
+    fn check_authority(&self) -> Result<()> {
+        let hook = get_mint_extension_data::<TransferHook>(&self.mint.to_account_info())?;
+        let authority = self.authority.key();
+        let allowed = match Option::<Pubkey>::from(hook.authority) {
+            Some(hook_authority) if hook_authority == authority => true,
+            Some(hook_authority) => self
+                .config
+                .as_ref()
+                .is_some_and(|config| config.key() == hook_authority && config.owner == authority),
+            None => false,
+        };
+        require!(allowed, ErrorCode::Unauthorized);
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 
-use crate::state::{MintError, Vault, VaultError, Whitelist};
+use crate::{
+    constant::{VAULT_AUTHORITY_SEED, VAULT_SEED},
+    error::ErrorCode,
+    state::Vault,
+};
 
 #[derive(Accounts)]
 pub struct MintToken<'info> {
@@ -16,28 +20,25 @@ pub struct MintToken<'info> {
     )]
     pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
-        seeds = [b"vault_authority"],
-        bump
-    )]
-    pub vault_authority: UncheckedAccount<'info>,
-    #[account(
-        seeds = [b"vault"],
+        seeds = [VAULT_SEED, mint.key().as_ref(), &vault.vault_id.to_le_bytes()],
         bump = vault.bump,
-        constraint = vault.mint == mint.key() @ VaultError::InvalidVaultMint
     )]
     pub vault: Account<'info, Vault>,
+    /// CHECK: PDA owning the vault token account
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> MintToken<'info> {
-    pub fn mint(&mut self, amount: u64, bumps: &MintTokenBumps) -> Result<()> {
-        require!(amount > 0, MintError::InvalidAmount);
-        require_keys_eq!(
-            self.vault.admin,
-            self.admin.key(),
-            MintError::UnauthorizedAdmin
-        );
+    /// The mint's authority must be the vault authority PDA, which signs the `mint_to`.
+    pub fn mint(&mut self, bumps: &MintTokenBumps, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require_keys_eq!(self.vault.admin, self.admin.key(), ErrorCode::Unauthorized);
 
         let cpi_accounts = MintTo {
             mint: self.mint.to_account_info(),
@@ -45,7 +46,17 @@ impl<'info> MintToken<'info> {
             authority: self.vault_authority.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        let vault_key = self.vault.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_AUTHORITY_SEED,
+            vault_key.as_ref(),
+            &[bumps.vault_authority],
+        ]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
 
         mint_to(cpi_ctx, amount)?;
         Ok(())
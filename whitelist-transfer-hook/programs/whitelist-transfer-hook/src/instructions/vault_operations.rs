@@ -0,0 +1,395 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::transfer_hook::TransferHook,
+    token_interface::{
+        burn, get_mint_extension_data, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount,
+        TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    constant::{INIT_CONFIG_SEED, VAULT_AUTHORITY_SEED, VAULT_POSITION_SEED, VAULT_SEED},
+    error::ErrorCode,
+    state::{Config, Vault, VaultPosition},
+};
+
+/// Tokens of a mint whose transfer hook is this program cannot be moved with a Token 2022
+/// transfer from here: Token 2022 would call back into this program, which the runtime rejects
+/// as reentrancy. `deposit`, `withdraw` and `distribute_rewards` burn them from the sender
+/// and mint them to the recipient instead, with the Config PDA as mint authority (as `clawback` does). That skips
+/// the hook, so the pause is checked here, and the vault position stands in for the hook's
+/// vault whitelist check.
+fn is_hooked_mint(mint: &InterfaceAccount<Mint>) -> bool {
+    get_mint_extension_data::<TransferHook>(&mint.to_account_info())
+        .is_ok_and(|hook| Option::<Pubkey>::from(hook.program_id) == Some(crate::ID))
+}
+
+/// Mints `amount` to `to`, signed by the Config PDA. Fails while transfers are paused, as the
+/// skipped hook would.
+fn mint_from_config<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    config: &Account<'info, Config>,
+    amount: u64,
+) -> Result<()> {
+    require!(!config.paused, ErrorCode::TransfersPaused);
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: config.to_account_info(),
+            },
+            &[&[INIT_CONFIG_SEED, &[config.bump]]],
+        ),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,token::mint=mint,token::authority=user,token::token_program=token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: PDA owning the vault token account
+    #[account(
+      seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+      bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = vault.token_account,
+        token::mint = mint,
+        token::authority = vault_authority,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_POSITION_SEED, vault.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, VaultPosition>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Deposit<'info> {
+    /// Mints shares for the tokens the vault actually received, which is less than `amount`
+    /// when the mint charges a transfer fee. Hooked mints are burned and minted, see
+    /// [`is_hooked_mint`].
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let balance_before = self.vault_token_account.amount;
+        if is_hooked_mint(&self.mint) {
+            let cpi_accounts = Burn {
+                mint: self.mint.to_account_info(),
+                from: self.user_token_account.to_account_info(),
+                authority: self.user.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            burn(cpi_ctx, amount)?;
+            mint_from_config(
+                &self.token_program,
+                &self.mint,
+                &self.vault_token_account,
+                &self.config,
+                amount,
+            )?;
+        } else {
+            let cpi_accounts = TransferChecked {
+                from: self.user_token_account.to_account_info(),
+                to: self.vault_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.user.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        }
+        self.vault_token_account.reload()?;
+        let received = self
+            .vault_token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let shares = self
+            .vault
+            .shares_for_assets(received)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        require!(shares > 0, ErrorCode::ZeroShares);
+
+        credit_shares(&mut self.vault, &mut self.position, received, shares)?;
+
+        msg!("Deposited {} tokens for {} shares", received, shares);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: PDA owning the vault token account
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = vault.token_account,
+        token::mint = mint,
+        token::authority = vault_authority,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_POSITION_SEED, vault.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, VaultPosition>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// Redeems `shares` for their share of the vault's assets, rounded down.
+    pub fn withdraw(&mut self, bumps: &WithdrawBumps, shares: u64) -> Result<()> {
+        require!(shares > 0, ErrorCode::InvalidAmount);
+        require!(
+            self.position.shares >= shares,
+            ErrorCode::InsufficientWhitelistedBalance
+        );
+
+        let amount = self
+            .vault
+            .assets_for_shares(shares)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        require!(amount > 0, ErrorCode::ZeroShares);
+
+        let vault_key = self.vault.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_AUTHORITY_SEED,
+            vault_key.as_ref(),
+            &[bumps.vault_authority],
+        ]];
+
+        if is_hooked_mint(&self.mint) {
+            let cpi_accounts = Burn {
+                mint: self.mint.to_account_info(),
+                from: self.vault_token_account.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            burn(cpi_ctx, amount)?;
+            mint_from_config(
+                &self.token_program,
+                &self.mint,
+                &self.user_token_account,
+                &self.config,
+                amount,
+            )?;
+        } else {
+            let cpi_accounts = TransferChecked {
+                from: self.vault_token_account.to_account_info(),
+                to: self.user_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        }
+
+        self.position.shares -= shares;
+        self.vault.total_shares = self
+            .vault
+            .total_shares
+            .checked_sub(shares)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        self.vault.total_assets = self
+            .vault
+            .total_assets
+            .checked_sub(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        msg!("Redeemed {} shares for {} tokens", shares, amount);
+        Ok(())
+    }
+}
+
+/// Adds `assets` to the vault and the `shares` minted for them to the vault and `position`.
+pub(crate) fn credit_shares(
+    vault: &mut Vault,
+    position: &mut VaultPosition,
+    assets: u64,
+    shares: u64,
+) -> Result<()> {
+    vault.total_assets = vault
+        .total_assets
+        .checked_add(assets)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    vault.total_shares = vault
+        .total_shares
+        .checked_add(shares)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    position.shares = position
+        .shares
+        .checked_add(shares)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    Ok(())
+}
+
+/// Adds tokens to the vault without minting shares, which raises the share price for everyone
+/// holding shares.
+#[derive(Accounts)]
+pub struct DistributeRewards<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin,
+        token::token_program = token_program
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        address = vault.token_account,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DistributeRewards<'info> {
+    pub fn distribute_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        // Without shares, the rewards would all go to whoever deposits next
+        require!(self.vault.total_shares > 0, ErrorCode::NoVaultShares);
+
+        let balance_before = self.vault_token_account.amount;
+        if is_hooked_mint(&self.mint) {
+            let cpi_accounts = Burn {
+                mint: self.mint.to_account_info(),
+                from: self.admin_token_account.to_account_info(),
+                authority: self.admin.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            burn(cpi_ctx, amount)?;
+            mint_from_config(
+                &self.token_program,
+                &self.mint,
+                &self.vault_token_account,
+                &self.config,
+                amount,
+            )?;
+        } else {
+            let cpi_accounts = TransferChecked {
+                from: self.admin_token_account.to_account_info(),
+                to: self.vault_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.admin.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        }
+        self.vault_token_account.reload()?;
+        let received = self
+            .vault_token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        self.vault.total_assets = self
+            .vault
+            .total_assets
+            .checked_add(received)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        msg!(
+            "Distributed {} tokens over {} shares",
+            received,
+            self.vault.total_shares
+        );
+        Ok(())
+    }
+}
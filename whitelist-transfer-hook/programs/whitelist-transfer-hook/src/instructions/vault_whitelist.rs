@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    constant::{
+        HOOK_VAULT_ID, INIT_CONFIG_SEED, VAULT_POSITION_SEED, VAULT_SEED, VAULT_WHITELIST_SEED,
+    },
+    error::ErrorCode,
+    instructions::{batch_operations::create_program_account, vault_operations::credit_shares},
+    state::{Config, Vault, VaultPosition, Whitelist, WhitelistEntry},
+};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddToVaultWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [VAULT_SEED, vault.mint.as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        init,
+        payer = admin,
+        space = VaultPosition::LEN,
+        seeds = [VAULT_POSITION_SEED, vault.key().as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, VaultPosition>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveFromVaultWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [VAULT_SEED, vault.mint.as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [VAULT_POSITION_SEED, vault.key().as_ref(), user.as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, VaultPosition>,
+}
+
+/// Moves entries of the legacy `Whitelist` vector into `VaultPosition` PDAs of `vault`, passed
+/// in `remaining_accounts` in the order of the vector's first entries. The legacy list gated the
+/// hook's vault, so only the mint's `HOOK_VAULT_ID` vault can receive it.
+#[derive(Accounts)]
+pub struct MigrateVaultWhitelist<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [INIT_CONFIG_SEED],
+        bump = config.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.mint.as_ref(), &HOOK_VAULT_ID.to_le_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(address = vault.token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [VAULT_WHITELIST_SEED],
+        bump = whitelist.bump,
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToVaultWhitelist<'info> {
+    pub fn add_to_vault_whitelist(
+        &mut self,
+        bumps: AddToVaultWhitelistBumps,
+        user: Pubkey,
+    ) -> Result<()> {
+        self.position.set_inner(VaultPosition {
+            vault: self.vault.key(),
+            user,
+            shares: 0,
+            bump: bumps.position,
+        });
+
+        msg!("Added to vault whitelist. User: {}", user);
+        Ok(())
+    }
+}
+
+impl<'info> RemoveFromVaultWhitelist<'info> {
+    /// Only positions without shares can be closed, so no deposit is left untracked.
+    pub fn remove_from_vault_whitelist(&mut self, user: Pubkey) -> Result<()> {
+        require!(self.position.shares == 0, ErrorCode::PositionNotEmpty);
+
+        msg!("Removed from vault whitelist. User: {}", user);
+        Ok(())
+    }
+}
+
+impl<'info> MigrateVaultWhitelist<'info> {
+    /// Run until the vector is empty, at which point the legacy account is closed. Each legacy
+    /// amount is credited as a deposit at the current share price, so the tokens must already be
+    /// in this vault's token account; the migration fails if they are not. A user who already
+    /// has a position keeps its shares.
+    pub fn migrate_vault_whitelist(
+        &mut self,
+        positions: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!positions.is_empty(), ErrorCode::EmptyBatch);
+        require!(
+            positions.len() <= self.whitelist.address.len(),
+            ErrorCode::BatchLengthMismatch
+        );
+
+        let vault = self.vault.key();
+        let entries: Vec<WhitelistEntry> =
+            self.whitelist.address.drain(..positions.len()).collect();
+        for (entry, account) in entries.iter().zip(positions) {
+            let (expected, bump) = Pubkey::find_program_address(
+                &[VAULT_POSITION_SEED, vault.as_ref(), entry.user.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(account.key(), expected, ErrorCode::InvalidPositionAccount);
+
+            let mut position = if account.owner == &crate::ID {
+                Account::<VaultPosition>::try_from(account)?.into_inner()
+            } else {
+                create_program_account(
+                    &self.owner.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    account,
+                    VaultPosition::LEN,
+                    &[&[VAULT_POSITION_SEED, vault.as_ref(), entry.user.as_ref(), &[bump]]],
+                )?;
+                VaultPosition {
+                    vault,
+                    user: entry.user,
+                    shares: 0,
+                    bump,
+                }
+            };
+            let shares = self
+                .vault
+                .shares_for_assets(entry.amount)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            credit_shares(&mut self.vault, &mut position, entry.amount, shares)?;
+            let mut data = account.try_borrow_mut_data()?;
+            position.try_serialize(&mut &mut data[..])?;
+
+            msg!("Migrated vault whitelist entry. User: {}", entry.user);
+        }
+        // The legacy list only recorded amounts, so check that the tokens behind them are there
+        require!(
+            self.vault_token_account.amount >= self.vault.total_assets,
+            ErrorCode::VaultUndercollateralized
+        );
+
+        if self.whitelist.address.is_empty() {
+            msg!("Vault whitelist drained");
+            return self.whitelist.close(self.owner.to_account_info());
+        }
+        self.shrink_whitelist()
+    }
+
+    /// Resizes the legacy account to its remaining entries and refunds the freed rent.
+    fn shrink_whitelist(&self) -> Result<()> {
+        let account_info = self.whitelist.to_account_info();
+        let new_account_size = 8 + 4 + self.whitelist.address.len() * WhitelistEntry::SIZE + 1;
+        let rent_diff = account_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_account_size));
+
+        account_info.resize(new_account_size)?;
+        if rent_diff > 0 {
+            account_info.sub_lamports(rent_diff)?;
+            self.owner.add_lamports(rent_diff)?;
+        }
+        Ok(())
+    }
+}
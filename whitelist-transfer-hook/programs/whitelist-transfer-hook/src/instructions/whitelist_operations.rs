@@ -1,7 +1,10 @@
 use crate::{
-    constant::{INIT_CONFIG_SEED, WHITELISTED_USER_SEED},
+    constant::{INIT_CONFIG_SEED, QUEUE_AUTHORITY_SEED, WHITELISTED_USER_SEED},
     error::ErrorCode,
-    state::{whitelist::WhitelistedUser, Config},
+    events::UserWhitelisted,
+    instructions::schedule_expiry::ExpiryTasks,
+    state::{whitelist::WhitelistedUser, Config, EntryExpiry, KycInfo, WhitelistMode},
+    tuktuk,
 };
 use anchor_lang::prelude::*;
 
@@ -9,55 +12,144 @@ use anchor_lang::prelude::*;
 #[instruction(user: Pubkey)]
 pub struct AddToWhitelist<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [INIT_CONFIG_SEED ,admin.key().as_ref()],
+        seeds = [INIT_CONFIG_SEED],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
     )]
     pub config: Account<'info, Config>,
     #[account(
         init,
-        payer = admin,
+        payer = authority,
         space = WhitelistedUser::LEN,
         seeds = [WHITELISTED_USER_SEED, user.as_ref()],
         bump
     )]
     pub whitelisted_user: Account<'info, WhitelistedUser>,
     pub system_program: Program<'info, System>,
+    // TukTuk accounts for the entry's `expire_user` task, required once `config.task_queue`
+    // is set in `Expiring` mode
+    /// CHECK: must be `config.task_queue`; TukTuk checks the rest.
+    #[account(mut)]
+    pub task_queue: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked by TukTuk.
+    pub task_queue_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: initialized by TukTuk.
+    #[account(mut)]
+    pub task: Option<UncheckedAccount<'info>>,
+    /// CHECK: initialized by TukTuk. Required while `config.expiry_notice` is set.
+    #[account(mut)]
+    pub notice_task: Option<UncheckedAccount<'info>>,
+    /// CHECK: via seeds; signs the `queue_task_v0` CPI.
+    #[account(seeds = [QUEUE_AUTHORITY_SEED], bump)]
+    pub queue_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: TukTuk program.
+    #[account(address = tuktuk::ID)]
+    pub tuktuk_program: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct RemoveFromWhitelist<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [INIT_CONFIG_SEED ,admin.key().as_ref()],
+        seeds = [INIT_CONFIG_SEED],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized,
+        constraint = config.can_manage_whitelist(&authority.key()) @ ErrorCode::Unauthorized,
     )]
     pub config: Account<'info, Config>,
     #[account(
         mut,
-        close = admin,
+        close = rent_payer,
         seeds = [WHITELISTED_USER_SEED, user.as_ref()],
         bump
     )]
     pub whitelisted_user: Account<'info, WhitelistedUser>,
+    /// CHECK: gets the rent back.
+    #[account(
+        mut,
+        address = whitelisted_user.rent_payer @ ErrorCode::InvalidRentPayer,
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> AddToWhitelist<'info> {
-    pub fn add_to_whitelist(&mut self, bump: AddToWhitelistBumps, user: Pubkey) -> Result<()> {
+    pub fn add_to_whitelist(
+        &mut self,
+        bump: AddToWhitelistBumps,
+        user: Pubkey,
+        kyc: KycInfo,
+        expiry: Option<EntryExpiry>,
+        task_id: Option<u16>,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        kyc.validate()?;
+        let added_at = Clock::get()?.unix_timestamp;
+        let expiry_timestamp = match expiry {
+            Some(expiry) => self.config.requested_expiry(added_at, expiry)?,
+            None => self.config.entry_expiry(added_at),
+        };
+
         self.whitelisted_user.set_inner(WhitelistedUser {
-            user: user,
+            user,
+            kyc,
+            added_by: self.authority.key(),
+            rent_payer: self.authority.key(),
+            added_at,
+            is_active: true,
+            expiry_timestamp,
             bump: bump.whitelisted_user,
         });
 
+        emit!(UserWhitelisted {
+            user,
+            kyc,
+            added_by: self.authority.key(),
+            timestamp: added_at,
+        });
+
+        if self.config.mode == WhitelistMode::Expiring && self.config.task_queue.is_some() {
+            self.schedule_expiry(bump.queue_authority, task_id, notice_task_id)?;
+        }
+
         msg!("Added to whitelist. User: {}", user.key());
         Ok(())
     }
+
+    fn schedule_expiry(
+        &self,
+        queue_authority_bump: Option<u8>,
+        task_id: Option<u16>,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        let (Some(task), Some(task_id)) = (&self.task, task_id) else {
+            return err!(ErrorCode::MissingExpiryTask);
+        };
+
+        ExpiryTasks::from_optional_accounts(
+            &self.config,
+            self.authority.to_account_info(),
+            self.system_program.to_account_info(),
+            &self.task_queue,
+            &self.task_queue_authority,
+            &self.queue_authority,
+            &self.tuktuk_program,
+            queue_authority_bump,
+        )?
+        .schedule(
+            &self.config,
+            &self.whitelisted_user,
+            task.to_account_info(),
+            task_id,
+            self.notice_task
+                .as_ref()
+                .zip(notice_task_id)
+                .map(|(task, id)| (task.to_account_info(), id)),
+        )
+    }
 }
 
 impl<'info> RemoveFromWhitelist<'info> {
@@ -4,15 +4,22 @@
 use anchor_lang::prelude::*;
 
 mod constant;
-mod error;
-mod instructions;
-mod state;
+pub mod ed25519;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod merkle;
+pub mod state;
+pub mod tuktuk;
 use instructions::*;
+use state::{BlackoutPeriod, EntryExpiry, KycInfo, TradingWindow, WhitelistMode};
+
+#[cfg(not(target_os = "solana"))]
+pub mod client;
 
 use spl_discriminator::SplDiscriminate;
-use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_transfer_hook_interface::instruction::{
-    ExecuteInstruction, InitializeExtraAccountMetaListInstruction,
+    ExecuteInstruction, UpdateExtraAccountMetaListInstruction,
 };
 
 declare_id!("EfvcbUrqid3P54BhoFLrJhAdJxe2vxKhGG9sDRvCsWHh");
@@ -21,19 +28,299 @@ declare_id!("EfvcbUrqid3P54BhoFLrJhAdJxe2vxKhGG9sDRvCsWHh");
 pub mod whitelist_transfer_hook {
     use super::*;
 
-    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
-        ctx.accounts.init_config(ctx.bumps)
+    pub fn init_config(ctx: Context<InitConfig>, mode: WhitelistMode) -> Result<()> {
+        ctx.accounts.init_config(ctx.bumps, mode)
+    }
+
+    pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+        ctx.accounts.propose_owner(new_owner)
+    }
+
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        ctx.accounts.accept_owner()
+    }
+
+    pub fn add_operator(ctx: Context<OperatorOperations>, operator: Pubkey) -> Result<()> {
+        ctx.accounts.add_operator(operator)
+    }
+
+    pub fn remove_operator(ctx: Context<OperatorOperations>, operator: Pubkey) -> Result<()> {
+        ctx.accounts.remove_operator(operator)
+    }
+
+    pub fn add_approved_program(
+        ctx: Context<ApprovedProgramOperations>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.add_approved_program(program_id)
+    }
+
+    pub fn remove_approved_program(
+        ctx: Context<ApprovedProgramOperations>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_approved_program(program_id)
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        ctx.accounts.pause()
+    }
+
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        ctx.accounts.unpause()
+    }
+
+    pub fn add_treasury(ctx: Context<TreasuryOperations>, token_account: Pubkey) -> Result<()> {
+        ctx.accounts.add_treasury(token_account)
+    }
+
+    pub fn remove_treasury(ctx: Context<TreasuryOperations>, token_account: Pubkey) -> Result<()> {
+        ctx.accounts.remove_treasury(token_account)
+    }
+
+    pub fn init_transfer_log(ctx: Context<InitTransferLog>) -> Result<()> {
+        ctx.accounts.init_transfer_log(ctx.bumps)
+    }
+
+    pub fn set_transfer_log(ctx: Context<SetTransferLog>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_transfer_log(enabled)
+    }
+
+    pub fn init_trading_schedule(
+        ctx: Context<InitTradingSchedule>,
+        windows: Vec<TradingWindow>,
+        blackouts: Vec<BlackoutPeriod>,
+    ) -> Result<()> {
+        ctx.accounts
+            .init_trading_schedule(ctx.bumps, windows, blackouts)
+    }
+
+    pub fn update_trading_schedule(
+        ctx: Context<TradingScheduleOperations>,
+        windows: Vec<TradingWindow>,
+        blackouts: Vec<BlackoutPeriod>,
+    ) -> Result<()> {
+        ctx.accounts.update_trading_schedule(windows, blackouts)
+    }
+
+    pub fn set_trading_schedule(
+        ctx: Context<TradingScheduleOperations>,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.set_trading_schedule(enabled)
     }
-    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, user: Pubkey) -> Result<()> {
-        ctx.accounts.add_to_whitelist(ctx.bumps, user)
+
+    pub fn add_to_whitelist(
+        ctx: Context<AddToWhitelist>,
+        user: Pubkey,
+        kyc: KycInfo,
+        expiry: Option<EntryExpiry>,
+        task_id: Option<u16>,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts
+            .add_to_whitelist(ctx.bumps, user, kyc, expiry, task_id, notice_task_id)
     }
 
     pub fn remove_from_whitelist(ctx: Context<RemoveFromWhitelist>, user: Pubkey) -> Result<()> {
         ctx.accounts.remove_from_whitelist(user)
     }
 
-    pub fn init_mint(ctx: Context<TokenFactory>, decimals: u8) -> Result<()> {
-        ctx.accounts.init_mint(ctx.bumps, decimals)
+    pub fn batch_add_to_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWhitelistOperations<'info>>,
+        users: Vec<Pubkey>,
+        kyc: KycInfo,
+    ) -> Result<()> {
+        ctx.accounts
+            .batch_add_to_whitelist(&users, kyc, ctx.remaining_accounts)
+    }
+
+    pub fn batch_remove_from_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWhitelistOperations<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .batch_remove_from_whitelist(&users, ctx.remaining_accounts)
+    }
+
+    pub fn set_merkle_root(
+        ctx: Context<SetMerkleRoot>,
+        merkle_root: Option<[u8; 32]>,
+        merkle_tier: u8,
+    ) -> Result<()> {
+        ctx.accounts.set_merkle_root(merkle_root, merkle_tier)
+    }
+
+    pub fn set_tier_rule(
+        ctx: Context<SetTierRule>,
+        source_tier: u8,
+        allowed_destination_tiers: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_tier_rule(source_tier, allowed_destination_tiers)
+    }
+
+    pub fn claim_whitelist(ctx: Context<ClaimWhitelist>, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.claim_whitelist(ctx.bumps, &proof)
+    }
+
+    pub fn prove_membership(
+        ctx: Context<ProveMembership>,
+        user: Pubkey,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.prove_membership(user, &proof)
+    }
+
+    pub fn set_kyc_provider(
+        ctx: Context<SetKycProvider>,
+        kyc_provider: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.set_kyc_provider(kyc_provider)
+    }
+
+    pub fn self_register(
+        ctx: Context<SelfRegister>,
+        kyc: KycInfo,
+        nonce: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .self_register(ctx.bumps, kyc, nonce, expires_at)
+    }
+
+    pub fn expire_user(ctx: Context<ExpireUser>, user: Pubkey) -> Result<()> {
+        ctx.accounts.expire_user(user)
+    }
+
+    pub fn reap_expired(ctx: Context<ReapExpired>, user: Pubkey) -> Result<()> {
+        ctx.accounts.reap_expired(user)
+    }
+
+    pub fn set_retention(
+        ctx: Context<SetRetention>,
+        retention_period: i64,
+        reap_bounty: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_retention(retention_period, reap_bounty)
+    }
+
+    pub fn set_task_queue(ctx: Context<SetTaskQueue>, task_queue: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.set_task_queue(task_queue)
+    }
+
+    pub fn schedule_expiry(
+        ctx: Context<ScheduleExpiry>,
+        user: Pubkey,
+        task_id: u16,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts
+            .schedule_expiry(ctx.bumps, user, task_id, notice_task_id)
+    }
+
+    pub fn notify_expiring(ctx: Context<NotifyExpiring>, user: Pubkey) -> Result<()> {
+        ctx.accounts.notify_expiring(user)
+    }
+
+    pub fn set_max_entry_duration(
+        ctx: Context<SetMaxEntryDuration>,
+        max_entry_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_max_entry_duration(max_entry_duration)
+    }
+
+    pub fn set_expiry_grace(
+        ctx: Context<SetExpiryGrace>,
+        grace_period: i64,
+        expiry_notice: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_expiry_grace(grace_period, expiry_notice)
+    }
+
+    pub fn renew_whitelist(
+        ctx: Context<RenewWhitelist>,
+        user: Pubkey,
+        expiry: EntryExpiry,
+        task_id: Option<u16>,
+        notice_task_id: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts
+            .renew_whitelist(ctx.bumps, user, expiry, task_id, notice_task_id)
+    }
+
+    pub fn add_to_vault_whitelist(ctx: Context<AddToVaultWhitelist>, user: Pubkey) -> Result<()> {
+        ctx.accounts.add_to_vault_whitelist(ctx.bumps, user)
+    }
+
+    pub fn remove_from_vault_whitelist(
+        ctx: Context<RemoveFromVaultWhitelist>,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_from_vault_whitelist(user)
+    }
+
+    pub fn migrate_vault_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateVaultWhitelist<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_vault_whitelist(ctx.remaining_accounts)
+    }
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>, vault_id: u16) -> Result<()> {
+        ctx.accounts.initialize_vault(&ctx.bumps, vault_id)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+        ctx.accounts.withdraw(&ctx.bumps, shares)
+    }
+
+    pub fn distribute_rewards(ctx: Context<DistributeRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.distribute_rewards(amount)
+    }
+
+    pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
+        ctx.accounts.mint(&ctx.bumps, amount)
+    }
+
+    pub fn init_mint(
+        ctx: Context<TokenFactory>,
+        decimals: u8,
+        extensions: MintExtensions,
+    ) -> Result<()> {
+        ctx.accounts.init_mint(ctx.bumps, decimals, extensions)
+    }
+
+    pub fn issue_tokens(ctx: Context<IssueTokens>, amount: u64) -> Result<()> {
+        ctx.accounts.issue_tokens(amount)
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        ctx.accounts.update_token_metadata(field, value)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        ctx.accounts.withdraw_fees()
+    }
+
+    pub fn freeze_holder(ctx: Context<HolderOperations>) -> Result<()> {
+        ctx.accounts.freeze_holder()
+    }
+
+    pub fn thaw_holder(ctx: Context<HolderOperations>) -> Result<()> {
+        ctx.accounts.thaw_holder()
+    }
+
+    pub fn clawback(ctx: Context<Clawback>, amount: u64) -> Result<()> {
+        ctx.accounts.clawback(amount)
     }
 
     #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
@@ -44,4 +331,13 @@ pub mod whitelist_transfer_hook {
     pub fn initialize_transfer_hook(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
         ctx.accounts.initialize_extra_account_meta_list(&ctx.bumps)
     }
+
+    #[instruction(discriminator = UpdateExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn update_extra_account_meta_list(
+        ctx: Context<UpdateExtraAccountMetaList>,
+        extra_account_metas: Vec<ExtraAccountMetaArgs>,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_extra_account_meta_list(extra_account_metas)
+    }
 }
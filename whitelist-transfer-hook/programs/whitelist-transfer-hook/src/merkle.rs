@@ -0,0 +1,26 @@
+//! Hashing shared by the on-chain proof check and the off-chain tree builder.
+//!
+//! Pairs are sorted before hashing, so a proof is just the list of sibling hashes.
+//! Leaves and inner nodes use different prefixes so an inner node can never be passed off as a leaf.
+
+use anchor_lang::prelude::Pubkey;
+use solana_sha256_hasher::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(user: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, user.as_ref()]).to_bytes()
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_proof(root: &[u8; 32], user: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf_hash(user), |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
@@ -1,11 +1,155 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    error::ErrorCode,
+    merkle,
+    state::{EntryExpiry, WhitelistedUser},
+};
+
+/// Which whitelist the hook enforces, chosen once at `init_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WhitelistMode {
+    /// Senders need a `WhitelistedUser` entry or a Merkle proof.
+    #[default]
+    PerUser,
+    /// Like `PerUser`, but entries get an expiry and are deactivated by `expire_user`.
+    Expiring,
+    /// Every transfer goes into or out of the vault, by users on the vault's `Whitelist`.
+    VaultGated,
+}
+
 #[account]
 pub struct Config {
-    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    pub operators: Vec<Pubkey>,
+    /// Root of the Merkle whitelist. `None` disables the Merkle mode.
+    pub merkle_root: Option<[u8; 32]>,
+    /// KYC tier of addresses whitelisted through the Merkle root.
+    pub merkle_tier: u8,
+    /// `tier_rules[source]` is a bitmask of the destination tiers `source` may transfer to.
+    pub tier_rules: [u8; Self::MAX_TIERS],
+    /// Key whose Ed25519 attestations let users `self_register`. `None` disables it.
+    pub kyc_provider: Option<Pubkey>,
+    /// Programs whose PDAs (escrows, DEX vaults) may send tokens without a whitelist entry.
+    pub approved_programs: Vec<Pubkey>,
+    /// Emergency halt: while set, only transfers between two `treasuries` go through.
+    pub paused: bool,
+    /// Token accounts that may still transfer to each other while paused.
+    pub treasuries: Vec<Pubkey>,
+    pub mode: WhitelistMode,
+    /// Mints whose transfers the hook appends to their `TransferLog`.
+    pub logged_mints: Vec<Pubkey>,
+    /// TradingSchedule the hook enforces. `None` allows transfers at any time.
+    pub trading_schedule: Option<Pubkey>,
+    /// Longest lifetime an `Expiring` mode entry can be given or renewed to, in seconds.
+    pub max_entry_duration: i64,
+    /// TukTuk task queue that `expire_user` tasks are queued on in `Expiring` mode. `None`
+    /// leaves expiring entries to be expired by hand.
+    pub task_queue: Option<Pubkey>,
+    /// Seconds after its expiry during which an `Expiring` mode entry can still send tokens
+    /// (to sell or redeem) but no longer receive them. 0 cuts entries off at expiry.
+    pub grace_period: i64,
+    /// Seconds before expiry at which a queued task emits `EntryExpiringSoon`. 0 queues no
+    /// notice task.
+    pub expiry_notice: i64,
+    /// Seconds an entry stays expired (and past its grace period) before anyone can close it
+    /// with `reap_expired`.
+    pub retention_period: i64,
+    /// Lamports of the reaped entry's rent paid to whoever runs `reap_expired`.
+    pub reap_bounty: u64,
     pub bump: u8,
 }
 
 impl Config {
-    pub const LEN: usize = 8 + 32 + 1;
+    pub const MAX_OPERATORS: usize = 10;
+    pub const MAX_APPROVED_PROGRAMS: usize = 10;
+    pub const MAX_TREASURIES: usize = 10;
+    pub const MAX_LOGGED_MINTS: usize = 10;
+    /// Lifetime of a new entry in `Expiring` mode when none is requested, in seconds.
+    pub const ENTRY_DURATION: i64 = 60;
+    pub const DEFAULT_MAX_ENTRY_DURATION: i64 = 365 * 24 * 60 * 60;
+    pub const DEFAULT_RETENTION_PERIOD: i64 = 30 * 24 * 60 * 60;
+    /// Covers the reaper's transaction fee.
+    pub const DEFAULT_REAP_BOUNTY: u64 = 5_000;
+    /// One bit per tier in a `tier_rules` mask.
+    pub const MAX_TIERS: usize = 8;
+    pub const LEN: usize = 8
+        + 32
+        + (1 + 32)
+        + (4 + 32 * Self::MAX_OPERATORS)
+        + (1 + 32)
+        + 1
+        + Self::MAX_TIERS
+        + (1 + 32)
+        + (4 + 32 * Self::MAX_APPROVED_PROGRAMS)
+        + 1
+        + (4 + 32 * Self::MAX_TREASURIES)
+        + 1
+        + (4 + 32 * Self::MAX_LOGGED_MINTS)
+        + (1 + 32)
+        + 8
+        + (1 + 32)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1;
+    /// Every tier may transfer to every tier.
+    pub const ALLOW_ALL_TIERS: [u8; Self::MAX_TIERS] = [u8::MAX; Self::MAX_TIERS];
+
+    /// The owner can do everything an operator can, plus change the config.
+    pub fn can_manage_whitelist(&self, key: &Pubkey) -> bool {
+        self.owner == *key || self.operators.contains(key)
+    }
+
+    /// `expiry_timestamp` for an entry created at `now`.
+    pub fn entry_expiry(&self, now: i64) -> i64 {
+        match self.mode {
+            WhitelistMode::Expiring => now + Self::ENTRY_DURATION,
+            WhitelistMode::PerUser | WhitelistMode::VaultGated => WhitelistedUser::NO_EXPIRY,
+        }
+    }
+
+    /// `expiry_timestamp` for an entry given `expiry` at `now`: in the future and at most
+    /// `max_entry_duration` away.
+    pub fn requested_expiry(&self, now: i64, expiry: EntryExpiry) -> Result<i64> {
+        require!(
+            self.mode == WhitelistMode::Expiring,
+            ErrorCode::NotExpiringMode
+        );
+        let expiry_timestamp = match expiry {
+            EntryExpiry::Duration(duration) => now.checked_add(duration),
+            EntryExpiry::At(timestamp) => Some(timestamp),
+        }
+        .ok_or(error!(ErrorCode::InvalidExpiry))?;
+        require!(
+            expiry_timestamp > now && expiry_timestamp - now <= self.max_entry_duration,
+            ErrorCode::InvalidExpiry
+        );
+        Ok(expiry_timestamp)
+    }
+
+    pub fn is_treasury_transfer(&self, source: &Pubkey, destination: &Pubkey) -> bool {
+        self.treasuries.contains(source) && self.treasuries.contains(destination)
+    }
+
+    pub fn can_transfer_between(&self, source_tier: u8, destination_tier: u8) -> bool {
+        (destination_tier as usize) < Self::MAX_TIERS
+            && self
+                .tier_rules
+                .get(source_tier as usize)
+                .is_some_and(|allowed| (allowed >> destination_tier) & 1 == 1)
+    }
+
+    pub fn verify_membership(&self, user: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        let root = self
+            .merkle_root
+            .ok_or(error!(ErrorCode::MerkleRootNotSet))?;
+        require!(
+            merkle::verify_proof(&root, user, proof),
+            ErrorCode::InvalidMerkleProof
+        );
+        Ok(())
+    }
 }
@@ -1,5 +1,11 @@
 pub mod config;
+pub mod trading_schedule;
+pub mod transfer_log;
+pub mod vault;
 pub mod whitelist;
 
 pub use config::*;
+pub use trading_schedule::*;
+pub use transfer_log::*;
+pub use vault::*;
 pub use whitelist::*;
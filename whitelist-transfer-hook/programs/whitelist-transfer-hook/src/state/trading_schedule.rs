@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Weekly window in seconds since Monday 00:00 UTC, `[open, close)`. A window cannot wrap past
+/// Sunday midnight; split it in two instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradingWindow {
+    pub open: u32,
+    pub close: u32,
+}
+
+/// Unix timestamps `[start, end)` during which no transfers are allowed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlackoutPeriod {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Market hours enforced by the hook while `Config::trading_schedule` points at it.
+#[account]
+pub struct TradingSchedule {
+    /// Weekly windows in which transfers are allowed. Empty means open all week.
+    pub windows: Vec<TradingWindow>,
+    pub blackouts: Vec<BlackoutPeriod>,
+    pub bump: u8,
+}
+
+impl TradingSchedule {
+    pub const SECONDS_PER_WEEK: u32 = 7 * 24 * 60 * 60;
+    /// The Unix epoch was a Thursday; the first Monday 00:00 UTC after it.
+    const FIRST_MONDAY: i64 = 4 * 24 * 60 * 60;
+    pub const MAX_WINDOWS: usize = 14;
+    pub const MAX_BLACKOUTS: usize = 16;
+    pub const LEN: usize = 8 + (4 + 8 * Self::MAX_WINDOWS) + (4 + 16 * Self::MAX_BLACKOUTS) + 1;
+
+    pub fn validate(windows: &[TradingWindow], blackouts: &[BlackoutPeriod]) -> Result<()> {
+        require!(
+            windows.len() <= Self::MAX_WINDOWS && blackouts.len() <= Self::MAX_BLACKOUTS,
+            ErrorCode::InvalidTradingSchedule
+        );
+        require!(
+            windows
+                .iter()
+                .all(|window| window.open < window.close && window.close <= Self::SECONDS_PER_WEEK),
+            ErrorCode::InvalidTradingSchedule
+        );
+        require!(
+            blackouts.iter().all(|period| period.start < period.end),
+            ErrorCode::InvalidTradingSchedule
+        );
+        Ok(())
+    }
+
+    /// Transfers at `now` fall inside a window and outside every blackout.
+    pub fn check(&self, now: i64) -> Result<()> {
+        require!(
+            !self
+                .blackouts
+                .iter()
+                .any(|period| period.start <= now && now < period.end),
+            ErrorCode::TradingBlackout
+        );
+
+        let second_of_week =
+            (now - Self::FIRST_MONDAY).rem_euclid(Self::SECONDS_PER_WEEK as i64) as u32;
+        require!(
+            self.windows.is_empty()
+                || self
+                    .windows
+                    .iter()
+                    .any(|window| window.open <= second_of_week && second_of_week < window.close),
+            ErrorCode::OutsideTradingHours
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u32 = 60 * 60;
+    const DAY: u32 = 24 * HOUR;
+    /// 2026-10-19 00:00 UTC, a Monday.
+    const MONDAY: i64 = 1_792_368_000;
+
+    fn schedule(blackouts: Vec<BlackoutPeriod>) -> TradingSchedule {
+        // Weekdays 09:00 to 17:00
+        let windows = (0..5)
+            .map(|day| TradingWindow {
+                open: day * DAY + 9 * HOUR,
+                close: day * DAY + 17 * HOUR,
+            })
+            .collect();
+        TradingSchedule {
+            windows,
+            blackouts,
+            bump: 255,
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> i64 {
+        MONDAY + (day * DAY + hour * HOUR) as i64
+    }
+
+    #[test]
+    fn allows_only_inside_weekly_windows() {
+        let schedule = schedule(vec![]);
+
+        assert!(schedule.check(at(0, 9)).is_ok());
+        assert!(schedule.check(at(4, 16)).is_ok());
+        // A week later, and before the epoch's first Monday
+        assert!(schedule.check(at(7, 12)).is_ok());
+        assert!(schedule.check(at(0, 12) - 7 * 4000 * DAY as i64).is_ok());
+
+        assert_eq!(
+            schedule.check(at(0, 8)),
+            Err(ErrorCode::OutsideTradingHours.into())
+        );
+        assert_eq!(
+            schedule.check(at(0, 17)),
+            Err(ErrorCode::OutsideTradingHours.into())
+        );
+        assert_eq!(
+            schedule.check(at(5, 12)),
+            Err(ErrorCode::OutsideTradingHours.into())
+        );
+    }
+
+    #[test]
+    fn blackouts_override_windows() {
+        let schedule = schedule(vec![BlackoutPeriod {
+            start: at(2, 0),
+            end: at(3, 0),
+        }]);
+
+        assert_eq!(
+            schedule.check(at(2, 12)),
+            Err(ErrorCode::TradingBlackout.into())
+        );
+        assert!(schedule.check(at(3, 12)).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_schedules() {
+        let window = |open, close| TradingWindow { open, close };
+        assert!(TradingSchedule::validate(&[window(0, DAY)], &[]).is_ok());
+        assert!(TradingSchedule::validate(&[window(DAY, DAY)], &[]).is_err());
+        assert!(TradingSchedule::validate(&[window(DAY, 8 * DAY)], &[]).is_err());
+        assert!(TradingSchedule::validate(&[], &[BlackoutPeriod { start: 5, end: 5 }]).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferLogEntry {
+    pub source_owner: Pubkey,
+    pub destination_owner: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl TransferLogEntry {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8;
+}
+
+/// Ring buffer of the last `CAPACITY` transfers of a mint, appended to by the hook while the
+/// mint is in `Config::logged_mints`. Once full, each transfer overwrites the oldest entry.
+///
+/// This is a short recent-activity view for on-chain readers, not a retention store: the
+/// hook also emits a `TransferLogged` event per transfer, which is the audit history.
+#[account]
+pub struct TransferLog {
+    pub mint: Pubkey,
+    /// Index the next entry is written to, which is also the oldest entry once full.
+    pub head: u32,
+    /// Transfers logged since creation, including overwritten ones.
+    pub total: u64,
+    pub entries: Vec<TransferLogEntry>,
+    pub bump: u8,
+}
+
+impl TransferLog {
+    pub const CAPACITY: usize = 32;
+    pub const LEN: usize = 8 + 32 + 4 + 8 + (4 + TransferLogEntry::LEN * Self::CAPACITY) + 1;
+
+    pub fn append(&mut self, entry: TransferLogEntry) {
+        let head = self.head as usize;
+        if head == self.entries.len() {
+            self.entries.push(entry);
+        } else {
+            self.entries[head] = entry;
+        }
+        self.head = ((head + 1) % Self::CAPACITY) as u32;
+        self.total += 1;
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &TransferLogEntry> {
+        let (newer, older) = self.entries.split_at(self.head as usize);
+        older.iter().chain(newer)
+    }
+}
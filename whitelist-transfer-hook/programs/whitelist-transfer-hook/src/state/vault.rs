@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+/// Token account that `VaultGated` transfers must go into or out of. One per mint and
+/// `vault_id`; the hook uses the mint's `HOOK_VAULT_ID` vault.
+#[account]
+pub struct Vault {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub vault_id: u16,
+    /// Tokens owed to share holders: deposits and rewards, less withdrawals. Tokens sent to
+    /// the token account any other way are not counted, so they cannot move the share price.
+    pub total_assets: u64,
+    pub total_shares: u64,
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const INIT_SPACE: usize = 32 + 32 + 32 + 2 + 8 + 8 + 1;
+
+    /// Shares minted for depositing `assets`, rounded down so existing holders never lose
+    /// value. The first deposit (or any deposit while no shares exist) mints one share per token.
+    pub fn shares_for_assets(&self, assets: u64) -> Option<u64> {
+        if self.total_shares == 0 {
+            return Some(assets);
+        }
+        mul_div(assets, self.total_shares, self.total_assets)
+    }
+
+    /// Tokens paid out for redeeming `shares`, rounded down so the vault never pays more than
+    /// the shares are worth.
+    pub fn assets_for_shares(&self, shares: u64) -> Option<u64> {
+        mul_div(shares, self.total_assets, self.total_shares)
+    }
+}
+
+/// `a * b / c` rounded down, in `u128` so the product cannot overflow.
+fn mul_div(a: u64, b: u64, c: u64) -> Option<u64> {
+    let quotient = (a as u128 * b as u128).checked_div(c as u128)?;
+    u64::try_from(quotient).ok()
+}
+
+/// A user's place on the vault whitelist, one PDA per user so that lookups are a derivation
+/// instead of a scan.
+#[account]
+pub struct VaultPosition {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    /// The user's shares of the vault's `total_assets`.
+    pub shares: u64,
+    pub bump: u8,
+}
+
+impl VaultPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WhitelistEntry {
+    pub user: Pubkey,
+    /// Tokens the user has deposited through `deposit` and not withdrawn.
+    pub amount: u64,
+}
+
+/// Legacy vault whitelist, superseded by `VaultPosition`. Still honoured by the hook until
+/// `migrate_vault_whitelist` has drained it.
+#[account]
+pub struct Whitelist {
+    pub address: Vec<WhitelistEntry>,
+    pub bump: u8,
+}
+
+impl WhitelistEntry {
+    pub const SIZE: usize = 32 + 8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(total_assets: u64, total_shares: u64) -> Vault {
+        Vault {
+            admin: Pubkey::default(),
+            mint: Pubkey::default(),
+            token_account: Pubkey::default(),
+            vault_id: 0,
+            total_assets,
+            total_shares,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn first_deposit_mints_one_share_per_token() {
+        assert_eq!(vault(0, 0).shares_for_assets(1_000), Some(1_000));
+        // Tokens left behind after every share was redeemed go to the next depositor
+        assert_eq!(vault(3, 0).shares_for_assets(1_000), Some(1_000));
+    }
+
+    #[test]
+    fn rewards_raise_the_share_price() {
+        // 1_000 shares backed by 1_000 deposited and 500 distributed tokens
+        let vault = vault(1_500, 1_000);
+        assert_eq!(vault.shares_for_assets(300), Some(200));
+        assert_eq!(vault.assets_for_shares(200), Some(300));
+        assert_eq!(vault.assets_for_shares(1_000), Some(1_500));
+    }
+
+    #[test]
+    fn rounding_favours_the_vault() {
+        let vault = vault(1_500, 1_000);
+        // 1 token is worth 2/3 of a share, which rounds down to nothing
+        assert_eq!(vault.shares_for_assets(1), Some(0));
+        assert_eq!(vault.shares_for_assets(2), Some(1));
+        // 1 share is worth 1.5 tokens
+        assert_eq!(vault.assets_for_shares(1), Some(1));
+        for assets in 1..100 {
+            let shares = vault.shares_for_assets(assets).unwrap();
+            assert!(vault.assets_for_shares(shares).unwrap() <= assets);
+        }
+    }
+
+    #[test]
+    fn large_amounts_do_not_overflow() {
+        assert_eq!(vault(0, 0).assets_for_shares(1), None);
+        let vault = vault(u64::MAX, u64::MAX / 2);
+        assert_eq!(vault.assets_for_shares(u64::MAX / 2), Some(u64::MAX));
+        assert_eq!(vault.shares_for_assets(u64::MAX), Some(u64::MAX / 2));
+        // More tokens than fit in a u64
+        assert_eq!(vault.assets_for_shares(u64::MAX), None);
+    }
+}
@@ -1,11 +1,106 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+
+/// Off-chain KYC outcome recorded with a whitelist entry.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KycInfo {
+    /// Index into `Config::tier_rules`. Tier 0 is also what a destination without an entry gets.
+    pub tier: u8,
+    /// ISO 3166-1 alpha-2 code, e.g. `*b"DE"`. `[0, 0]` when unknown.
+    pub country: [u8; 2],
+    /// Hash of the off-chain KYC record (or of its URI) that justifies the entry.
+    pub attestation: [u8; 32],
+}
+
+impl KycInfo {
+    pub const LEN: usize = 1 + 2 + 32;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            (self.tier as usize) < crate::state::Config::MAX_TIERS,
+            ErrorCode::InvalidKycTier
+        );
+        require!(
+            self.country == [0, 0] || self.country.iter().all(u8::is_ascii_uppercase),
+            ErrorCode::InvalidCountryCode
+        );
+        Ok(())
+    }
+}
+
+/// Expiry requested for an `Expiring` mode entry, bounded by `Config::max_entry_duration`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryExpiry {
+    /// Seconds from now.
+    Duration(i64),
+    /// Unix timestamp.
+    At(i64),
+}
+
 #[account]
 pub struct WhitelistedUser {
     pub user: Pubkey,
+    pub kyc: KycInfo,
+    /// Owner or operator who added the entry, or the user for a self-registration.
+    pub added_by: Pubkey,
+    /// Paid the entry's rent, and gets it back when the entry is reaped.
+    pub rent_payer: Pubkey,
+    pub added_at: i64,
+    /// Cleared by `expire_user` in `Expiring` mode.
+    pub is_active: bool,
+    /// `NO_EXPIRY` outside `Expiring` mode.
+    pub expiry_timestamp: i64,
     pub bump: u8,
 }
 
 impl WhitelistedUser {
-    pub const LEN: usize = 8 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + KycInfo::LEN + 32 + 32 + 8 + 1 + 8 + 1;
+    pub const NO_EXPIRY: i64 = i64::MAX;
+
+    /// Entry still lets its user transfer at `now`. `expire_user` only records what this
+    /// already enforces.
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.is_active && now < self.expiry_timestamp
+    }
+
+    /// Expired at `now`, but within `grace_period` seconds of its expiry: the user may still
+    /// send, but not receive.
+    pub fn is_in_grace_at(&self, now: i64, grace_period: i64) -> bool {
+        now >= self.expiry_timestamp && now < self.expiry_timestamp.saturating_add(grace_period)
+    }
+
+    /// Past its grace period and expired for at least `retention_period` seconds at `now`, so
+    /// `reap_expired` may close it.
+    pub fn is_reapable_at(&self, now: i64, grace_period: i64, retention_period: i64) -> bool {
+        now >= self
+            .expiry_timestamp
+            .saturating_add(grace_period.max(retention_period))
+    }
+}
+
+/// Compact record of a Merkle claim, so the user does not have to prove membership in every
+/// transfer. It only counts while `root` is `Config.merkle_root`: publishing a root without the
+/// user revokes it, and claiming again under the new root renews it.
+#[account]
+pub struct MerkleClaim {
+    pub user: Pubkey,
+    pub root: [u8; 32],
+    pub bump: u8,
+}
+
+impl MerkleClaim {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+/// Marks a KYC attestation nonce as spent, so a signed attestation registers a user only once.
+#[account]
+pub struct UsedKycNonce {
+    pub user: Pubkey,
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+impl UsedKycNonce {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
 }
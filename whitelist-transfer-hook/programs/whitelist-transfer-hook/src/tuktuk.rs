@@ -0,0 +1,208 @@
+//! The part of the TukTuk program's interface used to queue `expire_user` tasks.
+//!
+//! Mirrors the `queue_task_v0` instruction of TukTuk's IDL. The `tuktuk-program` crate is not
+//! used since it pins an older Anchor release than this program.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+declare_id!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
+
+const QUEUE_TASK_V0_DISCRIMINATOR: [u8; 8] = [177, 95, 195, 252, 241, 2, 178, 88];
+const TASK_SEED: &[u8] = b"task";
+const TASK_QUEUE_AUTHORITY_SEED: &[u8] = b"task_queue_authority";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QueueTaskArgsV0 {
+    pub id: u16,
+    pub trigger: TriggerV0,
+    pub transaction: TransactionSourceV0,
+    pub crank_reward: Option<u64>,
+    pub free_tasks: u8,
+    pub description: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerV0 {
+    Now,
+    Timestamp(i64),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionSourceV0 {
+    CompiledV0(CompiledTransactionV0),
+    RemoteV0 { url: String, signer: Pubkey },
+}
+
+/// Instructions run by the crank, with their accounts ordered writable signers, read-only
+/// signers, writable, read-only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompiledTransactionV0 {
+    pub num_rw_signers: u8,
+    pub num_ro_signers: u8,
+    pub num_rw: u8,
+    pub accounts: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstructionV0>,
+    pub signer_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompiledInstructionV0 {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+pub struct QueueTaskV0<'info> {
+    pub payer: AccountInfo<'info>,
+    pub queue_authority: AccountInfo<'info>,
+    pub task_queue_authority: AccountInfo<'info>,
+    pub task_queue: AccountInfo<'info>,
+    pub task: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Account TukTuk creates for task `id` of `task_queue`.
+pub fn task_pda(task_queue: &Pubkey, id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[TASK_SEED, task_queue.as_ref(), &id.to_le_bytes()], &ID).0
+}
+
+/// Marks `queue_authority` as allowed to queue tasks on `task_queue`.
+pub fn task_queue_authority_pda(task_queue: &Pubkey, queue_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            TASK_QUEUE_AUTHORITY_SEED,
+            task_queue.as_ref(),
+            queue_authority.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Compiles `instructions` into the form TukTuk stores in a task. Accounts signed for by
+/// TukTuk's `signer_seeds` are not supported, so no account may be a signer.
+pub fn compile_transaction(instructions: &[Instruction]) -> CompiledTransactionV0 {
+    let mut metas: Vec<AccountMeta> = Vec::new();
+    for instruction in instructions {
+        for meta in instruction
+            .accounts
+            .iter()
+            .cloned()
+            .chain([AccountMeta::new_readonly(instruction.program_id, false)])
+        {
+            match metas.iter_mut().find(|known| known.pubkey == meta.pubkey) {
+                Some(known) => known.is_writable |= meta.is_writable,
+                None => metas.push(meta),
+            }
+        }
+    }
+    // Stable, so accounts keep their first-seen order within each group
+    metas.sort_by_key(|meta| !meta.is_writable);
+
+    let index = |key: &Pubkey| metas.iter().position(|meta| meta.pubkey == *key).unwrap() as u8;
+    CompiledTransactionV0 {
+        num_rw_signers: 0,
+        num_ro_signers: 0,
+        num_rw: metas.iter().filter(|meta| meta.is_writable).count() as u8,
+        accounts: metas.iter().map(|meta| meta.pubkey).collect(),
+        instructions: instructions
+            .iter()
+            .map(|instruction| CompiledInstructionV0 {
+                program_id_index: index(&instruction.program_id),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| index(&meta.pubkey))
+                    .collect(),
+                data: instruction.data.clone(),
+            })
+            .collect(),
+        signer_seeds: vec![],
+    }
+}
+
+pub fn queue_task_v0<'info>(
+    program: AccountInfo<'info>,
+    accounts: QueueTaskV0<'info>,
+    args: QueueTaskArgsV0,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = QUEUE_TASK_V0_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.queue_authority.key(), true),
+            AccountMeta::new_readonly(accounts.task_queue_authority.key(), false),
+            AccountMeta::new(accounts.task_queue.key(), false),
+            AccountMeta::new(accounts.task.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.payer,
+            accounts.queue_authority,
+            accounts.task_queue_authority,
+            accounts.task_queue,
+            accounts.task,
+            accounts.system_program,
+            program,
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiled_accounts_are_writable_first() {
+        let (program, config, entry) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let instruction = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(entry, false),
+            ],
+            data: vec![1, 2],
+        };
+
+        let compiled = compile_transaction(&[instruction]);
+
+        assert_eq!(compiled.accounts, vec![entry, config, program]);
+        assert_eq!(compiled.num_rw, 1);
+        assert_eq!(
+            compiled.instructions,
+            vec![CompiledInstructionV0 {
+                program_id_index: 2,
+                accounts: vec![1, 0],
+                data: vec![1, 2],
+            }]
+        );
+    }
+
+    #[test]
+    fn queue_task_discriminator_matches_anchor() {
+        assert_eq!(
+            QUEUE_TASK_V0_DISCRIMINATOR,
+            solana_sha256_hasher::hash(b"global:queue_task_v0").to_bytes()[..8]
+        );
+    }
+}
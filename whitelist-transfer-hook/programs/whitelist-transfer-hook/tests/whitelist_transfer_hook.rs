@@ -0,0 +1,3263 @@
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{
+            bpf_loader_upgradeable,
+            instruction::{AccountMeta as AnchorAccountMeta, Instruction as AnchorInstruction},
+            program_option::COption,
+            program_pack::Pack,
+            sysvar,
+        },
+        system_program, AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas,
+    },
+    anchor_spl::{
+        associated_token::{
+            self, get_associated_token_address_with_program_id,
+            spl_associated_token_account::instruction::create_associated_token_account,
+        },
+        token_2022::spl_token_2022::{
+            extension::{
+                default_account_state::DefaultAccountState, metadata_pointer::MetadataPointer,
+                permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig,
+                transfer_hook::TransferHook, BaseStateWithExtensions, ExtensionType,
+                StateWithExtensions,
+            },
+            instruction::transfer_checked as token_transfer_checked,
+            state::{Account as TokenAccount, AccountState, Mint},
+            ID as TOKEN_2022_PROGRAM_ID,
+        },
+        token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata,
+    },
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_account::Account,
+    solana_address::Address,
+    solana_clock::Clock,
+    solana_instruction::{account_meta::AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+    spl_transfer_hook_interface::instruction::ExecuteInstruction,
+    tuktuk_mock::{
+        crank::{crank, queued_tasks},
+        TaskV0, TriggerV0,
+    },
+    whitelist_transfer_hook::{
+        client::{
+            add_approved_program_instruction, add_treasury_instruction,
+            attestation::{self_register_instructions, set_kyc_provider_instruction},
+            batch::batch_add_instruction,
+            config_pda, extra_account_meta_list_pda,
+            merkle::{
+                claim_whitelist_instruction, prove_membership_instruction,
+                set_merkle_root_instruction, MerkleTree,
+            },
+            merkle_claim_pda, migrate_vault_whitelist_instruction, mint_pda,
+            notify_expiring_instruction, pause_instruction, program_data_pda, queue_authority_pda,
+            reap_expired_instruction, remove_approved_program_instruction,
+            set_expiry_grace_instruction, set_retention_instruction, set_task_queue_instruction,
+            trading_schedule_pda, transfer_checked_instruction,
+            transfer_log::{
+                decode_transfer_log, init_transfer_log_instruction, set_transfer_log_instruction,
+                transfer_log_pda,
+            },
+            unpause_instruction, update_extra_account_meta_list_instruction, vault_authority_pda,
+            vault_pda, vault_position_pda, vault_token_account_pda, vault_whitelist_pda,
+            whitelisted_user_pda, HOOK_VAULT_ID,
+        },
+        error::ErrorCode,
+        instructions::{
+            InitializeExtraAccountMetaList, KycAttestation, MetadataField, MintExtensions,
+            TokenMetadataArgs, TransferFeeArgs,
+        },
+        state::{
+            BlackoutPeriod, Config, EntryExpiry, KycInfo, MerkleClaim, TradingSchedule,
+            TradingWindow, Vault, VaultPosition, Whitelist, WhitelistEntry, WhitelistMode,
+            WhitelistedUser,
+        },
+        tuktuk::{self, task_pda, task_queue_authority_pda},
+    },
+};
+
+// Built by `anchor build`; Token 2022 and the associated token program ship with LiteSVM
+const PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/whitelist_transfer_hook.so"
+);
+// Built by `anchor build` in escrow-litesvm
+const ESCROW_PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../../escrow-litesvm/target/deploy/anchor_escrow.so"
+);
+// Built by `anchor build` in tuktuk-mock, deployed at TukTuk's address
+const TUKTUK_PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../../tuktuk-mock/target/deploy/tuktuk_mock.so"
+);
+const ESCROW_SEED: u64 = 7;
+/// 2026-10-19 00:00 UTC, a Monday.
+const MONDAY: i64 = 1_792_368_000;
+const HOUR: i64 = 60 * 60;
+const DECIMALS: u8 = 9;
+const MINT_AMOUNT: u64 = 100 * 10u64.pow(DECIMALS as u32);
+const TRANSFER_AMOUNT: u64 = 10u64.pow(DECIMALS as u32);
+
+#[test]
+fn init_config_sets_owner() {
+    let (mut svm, owner) = setup();
+
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+
+    let config: Config = load(&svm, &config_pda());
+    assert_eq!(config.owner, pubkey(&owner));
+    assert_eq!(config.pending_owner, None);
+    assert!(config.operators.is_empty());
+    assert_eq!(config.merkle_root, None);
+    assert_eq!(config.mode, WhitelistMode::PerUser);
+
+    // The config is a singleton
+    svm.expire_blockhash();
+    assert!(send(&mut svm, &[init_config_ix(&owner)], &[&owner]).is_err());
+}
+
+#[test]
+fn only_the_upgrade_authority_initializes_the_config() {
+    let (mut svm, _) = setup();
+    let stranger = Keypair::new();
+    svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    let result = send(&mut svm, &[init_config_ix(&stranger)], &[&stranger]);
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    assert!(svm.get_account(&address(&config_pda())).is_none());
+}
+
+#[test]
+fn init_mint_extension_layout() {
+    let (mut svm, owner) = setup();
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+
+    let extensions = MintExtensions {
+        metadata: Some(TokenMetadataArgs {
+            name: "Whitelist Token".to_string(),
+            symbol: "WLT".to_string(),
+            uri: "https://example.com/wlt.json".to_string(),
+        }),
+        default_frozen: true,
+        transfer_fee: Some(TransferFeeArgs {
+            basis_points: 50,
+            maximum_fee: 1_000,
+        }),
+    };
+    send(&mut svm, &[init_mint_ix(&owner, extensions)], &[&owner]).expect("init_mint");
+
+    let account = svm.get_account(&address(&mint_pda())).unwrap();
+    assert_eq!(account.owner, address(&TOKEN_2022_PROGRAM_ID));
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+
+    let mut extension_types = mint.get_extension_types().unwrap();
+    extension_types.sort_by_key(|extension| *extension as u16);
+    let mut expected = vec![
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::DefaultAccountState,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::TransferHook,
+        ExtensionType::MetadataPointer,
+        ExtensionType::TokenMetadata,
+    ];
+    expected.sort_by_key(|extension| *extension as u16);
+    assert_eq!(extension_types, expected);
+
+    assert_eq!(mint.base.decimals, DECIMALS);
+    assert_eq!(mint.base.mint_authority, Some(config_pda()).into());
+    assert_eq!(mint.base.freeze_authority, Some(config_pda()).into());
+
+    let hook = mint.get_extension::<TransferHook>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(hook.authority), Some(config_pda()));
+    assert_eq!(
+        Option::<Pubkey>::from(hook.program_id),
+        Some(whitelist_transfer_hook::ID)
+    );
+    let delegate = mint.get_extension::<PermanentDelegate>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(delegate.delegate),
+        Some(config_pda())
+    );
+    let pointer = mint.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(pointer.metadata_address),
+        Some(mint_pda())
+    );
+    assert_eq!(
+        Option::<Pubkey>::from(pointer.authority),
+        Some(config_pda())
+    );
+    let default_state = mint.get_extension::<DefaultAccountState>().unwrap();
+    assert_eq!(default_state.state, AccountState::Frozen as u8);
+    let fee = mint.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(
+        u16::from(fee.newer_transfer_fee.transfer_fee_basis_points),
+        50
+    );
+    assert_eq!(u64::from(fee.newer_transfer_fee.maximum_fee), 1_000);
+    assert_eq!(
+        Option::<Pubkey>::from(fee.transfer_fee_config_authority),
+        Some(config_pda())
+    );
+    assert_eq!(
+        Option::<Pubkey>::from(fee.withdraw_withheld_authority),
+        Some(config_pda())
+    );
+
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(metadata.name, "Whitelist Token");
+    assert_eq!(metadata.symbol, "WLT");
+    assert_eq!(metadata.uri, "https://example.com/wlt.json");
+    assert_eq!(
+        Option::<Pubkey>::from(metadata.update_authority),
+        Some(config_pda())
+    );
+}
+
+#[test]
+fn only_the_owner_updates_token_metadata() {
+    let (mut svm, owner) = setup();
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+    let extensions = MintExtensions {
+        metadata: Some(TokenMetadataArgs {
+            name: "Whitelist Token".to_string(),
+            symbol: "WLT".to_string(),
+            uri: "https://example.com/wlt.json".to_string(),
+        }),
+        ..MintExtensions::default()
+    };
+    send(&mut svm, &[init_mint_ix(&owner, extensions)], &[&owner]).expect("init_mint");
+
+    let stranger = Keypair::new();
+    svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let uri = "https://example.com/metadata/whitelist-token-v2.json";
+    let result = send(
+        &mut svm,
+        &[update_token_metadata_ix(&stranger, MetadataField::Uri, uri)],
+        &[&stranger],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    // The longer value grows the mint, and the owner pays for the extra rent
+    send(
+        &mut svm,
+        &[
+            update_token_metadata_ix(&owner, MetadataField::Uri, uri),
+            update_token_metadata_ix(&owner, MetadataField::Key("issuer".to_string()), "ACME"),
+        ],
+        &[&owner],
+    )
+    .expect("update_token_metadata");
+    let account = svm.get_account(&address(&mint_pda())).unwrap();
+    assert!(account.lamports >= svm.minimum_balance_for_rent_exemption(account.data.len()));
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(metadata.uri, uri);
+    assert_eq!(
+        metadata.additional_metadata,
+        vec![("issuer".to_string(), "ACME".to_string())]
+    );
+}
+
+#[test]
+fn initialize_transfer_hook_creates_meta_list() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+
+    let meta_list = svm
+        .get_account(&address(&extra_account_meta_list_pda(&mint)))
+        .expect("ExtraAccountMetaList account");
+    assert_eq!(meta_list.owner, address(&whitelist_transfer_hook::ID));
+
+    // Once per mint
+    svm.expire_blockhash();
+    assert!(send(
+        &mut svm,
+        &[initialize_transfer_hook_ix(&owner, &mint)],
+        &[&owner]
+    )
+    .is_err());
+}
+
+#[test]
+fn transfer_hook_authority_updates_meta_list() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let meta_list = address(&extra_account_meta_list_pda(&mint));
+    let current = InitializeExtraAccountMetaList::extra_account_metas().unwrap();
+
+    // Grow by one fixed account, then shrink back to the program's layout
+    let mut grown = current.clone();
+    grown.push(
+        ExtraAccountMeta::new_with_pubkey(&Pubkey::new_unique().to_bytes().into(), false, false)
+            .unwrap(),
+    );
+    for metas in [grown, current] {
+        let update = update_extra_account_meta_list_instruction(&pubkey(&owner), &mint, &metas);
+        svm.expire_blockhash();
+        send(&mut svm, &[update], &[&owner]).expect("update_extra_account_meta_list");
+
+        let account = svm.get_account(&meta_list).unwrap();
+        let mut expected = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut expected, &metas).unwrap();
+        assert_eq!(account.data, expected);
+        assert_eq!(
+            account.lamports,
+            svm.minimum_balance_for_rent_exemption(expected.len())
+        );
+    }
+}
+
+#[test]
+fn only_transfer_hook_authority_updates_meta_list() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let before = svm
+        .get_account(&address(&extra_account_meta_list_pda(&mint)))
+        .unwrap();
+
+    let update = update_extra_account_meta_list_instruction(&pubkey(&intruder), &mint, &[]);
+    assert!(send(&mut svm, &[update], &[&intruder]).is_err());
+
+    let after = svm
+        .get_account(&address(&extra_account_meta_list_pda(&mint)))
+        .unwrap();
+    assert_eq!(after.data, before.data);
+}
+
+#[test]
+fn whitelisted_owner_can_transfer() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&pubkey(&sender)));
+    assert_eq!(entry.user, pubkey(&sender));
+    assert_eq!(entry.kyc, kyc(1));
+    assert_eq!(entry.added_by, pubkey(&owner));
+    assert_eq!(entry.added_at, svm.get_sysvar::<Clock>().unix_timestamp);
+    assert!(entry.is_active);
+    assert_eq!(entry.expiry_timestamp, WhitelistedUser::NO_EXPIRY);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    send(&mut svm, &[transfer], &[&sender]).expect("whitelisted transfer");
+
+    assert_eq!(balance(&svm, &sender_ata), MINT_AMOUNT - TRANSFER_AMOUNT);
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+}
+
+#[test]
+fn non_whitelisted_owner_cannot_transfer() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+
+    assert_custom_error(result, ErrorCode::NotWhiteListed);
+    assert_eq!(balance(&svm, &sender_ata), MINT_AMOUNT);
+}
+
+#[test]
+fn tier_rules_gate_transfers() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient = Keypair::new();
+    let recipient_ata = token_account(&mut svm, &owner, &recipient, &mint);
+    let outsider_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    send(
+        &mut svm,
+        &[
+            add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(2)),
+            add_to_whitelist_ix(&owner, &pubkey(&recipient), kyc(1)),
+            // Tier 2 may only send to tier 2
+            set_tier_rule_ix(&owner, 2, 0b100),
+        ],
+        &[&owner],
+    )
+    .expect("whitelist and tier rule");
+
+    for destination in [recipient_ata, outsider_ata] {
+        let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &destination);
+        let result = send(&mut svm, &[transfer], &[&sender]);
+        assert_custom_error(result, ErrorCode::TierTransferNotAllowed);
+    }
+
+    // Opening tier 1 lets the whitelisted recipient in, but not the tier 0 outsider
+    send(&mut svm, &[set_tier_rule_ix(&owner, 2, 0b110)], &[&owner]).expect("set_tier_rule");
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    send(&mut svm, &[transfer], &[&sender]).expect("tier 2 -> tier 1");
+    svm.expire_blockhash();
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &outsider_ata);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+    assert_custom_error(result, ErrorCode::TierTransferNotAllowed);
+
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+    assert_eq!(balance(&svm, &outsider_ata), 0);
+}
+
+#[test]
+fn add_to_whitelist_validates_kyc() {
+    let (mut svm, owner) = setup();
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+    let user = Pubkey::new_unique();
+
+    let lowercase = KycInfo {
+        country: *b"de",
+        ..kyc(1)
+    };
+    let result = send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &user, lowercase)],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidCountryCode);
+
+    let result = send(
+        &mut svm,
+        &[add_to_whitelist_ix(
+            &owner,
+            &user,
+            kyc(Config::MAX_TIERS as u8),
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidKycTier);
+}
+
+#[test]
+fn self_register_with_provider_attestation() {
+    let (mut svm, owner) = setup();
+    let provider = Keypair::new();
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(
+        &mut svm,
+        &[
+            init_config_ix(&owner),
+            set_kyc_provider_instruction(&pubkey(&owner), Some(pubkey(&provider))),
+        ],
+        &[&owner],
+    )
+    .expect("config with KYC provider");
+
+    let attestation = attestation(&svm, &user, 1, 3600);
+    let register = self_register_ix(&provider, &attestation);
+    send(&mut svm, &register, &[&user]).expect("self_register");
+
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&pubkey(&user)));
+    assert_eq!(entry.user, pubkey(&user));
+    assert_eq!(entry.kyc, attestation.kyc);
+    assert_eq!(entry.added_by, pubkey(&provider));
+
+    // The rent goes back to the user who paid it, not to the authority removing the entry
+    let entry_key = whitelisted_user_pda(&pubkey(&user));
+    let result = send(
+        &mut svm,
+        &[remove_from_whitelist_ix(
+            &owner,
+            &pubkey(&user),
+            &pubkey(&owner),
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidRentPayer);
+    let rent = svm.get_account(&address(&entry_key)).unwrap().lamports;
+    let user_lamports = svm.get_balance(&user.pubkey()).unwrap();
+    send(
+        &mut svm,
+        &[remove_from_whitelist_ix(
+            &owner,
+            &pubkey(&user),
+            &pubkey(&user),
+        )],
+        &[&owner],
+    )
+    .expect("remove_from_whitelist");
+    assert_eq!(
+        svm.get_balance(&user.pubkey()).unwrap(),
+        user_lamports + rent
+    );
+
+    // The nonce is spent: removing the entry does not make the attestation reusable
+    svm.expire_blockhash();
+    assert!(send(&mut svm, &register, &[&user]).is_err());
+}
+
+#[test]
+fn self_register_rejects_forged_and_expired_attestations() {
+    let (mut svm, owner) = setup();
+    let provider = Keypair::new();
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+
+    let valid = attestation(&svm, &user, 1, 3600);
+    let result = send(&mut svm, &self_register_ix(&provider, &valid), &[&user]);
+    assert_custom_error(result, ErrorCode::KycProviderNotSet);
+
+    send(
+        &mut svm,
+        &[set_kyc_provider_instruction(
+            &pubkey(&owner),
+            Some(pubkey(&provider)),
+        )],
+        &[&owner],
+    )
+    .expect("set_kyc_provider");
+
+    // Validly signed, but not by the configured provider
+    let result = send(
+        &mut svm,
+        &self_register_ix(&Keypair::new(), &valid),
+        &[&user],
+    );
+    assert_custom_error(result, ErrorCode::InvalidAttestation);
+
+    let expired = attestation(&svm, &user, 1, -1);
+    let result = send(&mut svm, &self_register_ix(&provider, &expired), &[&user]);
+    assert_custom_error(result, ErrorCode::AttestationExpired);
+
+    assert!(svm
+        .get_account(&address(&whitelisted_user_pda(&pubkey(&user))))
+        .is_none());
+}
+
+#[test]
+fn approved_program_pda_sends_from_escrow() {
+    let (mut svm, owner) = setup();
+    add_escrow_program(&mut svm);
+    let mint = setup_mint(&mut svm, &owner);
+    let (maker, maker_ata) = funded_holder(&mut svm, &owner, &mint);
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &pubkey(&maker), kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+
+    // Depositing only needs the maker to be whitelisted; the vault owner counts as tier 0
+    send(&mut svm, &[escrow_make_ix(&maker, &mint)], &[&maker]).expect("make");
+    let vault = escrow_vault(&maker, &mint);
+    assert_eq!(balance(&svm, &vault), TRANSFER_AMOUNT);
+
+    // The escrow PDA has no whitelist entry
+    let result = send(&mut svm, &[escrow_refund_ix(&maker, &mint)], &[&maker]);
+    assert_custom_error(result, ErrorCode::NotWhiteListed);
+
+    let approve = add_approved_program_instruction(&pubkey(&owner), &anchor_escrow::ID);
+    send(&mut svm, &[approve], &[&owner]).expect("add_approved_program");
+    let config: Config = load(&svm, &config_pda());
+    assert_eq!(config.approved_programs, vec![anchor_escrow::ID]);
+
+    svm.expire_blockhash();
+    send(&mut svm, &[escrow_refund_ix(&maker, &mint)], &[&maker]).expect("refund");
+    assert_eq!(balance(&svm, &maker_ata), MINT_AMOUNT);
+    assert!(svm
+        .get_account(&address(&vault))
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn only_owner_manages_approved_programs() {
+    let (mut svm, owner) = setup();
+    setup_mint(&mut svm, &owner);
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let program_id = anchor_escrow::ID;
+
+    let approve = add_approved_program_instruction(&pubkey(&intruder), &program_id);
+    let result = send(&mut svm, &[approve], &[&intruder]);
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    let approve = add_approved_program_instruction(&pubkey(&owner), &program_id);
+    send(&mut svm, std::slice::from_ref(&approve), &[&owner]).expect("add_approved_program");
+    svm.expire_blockhash();
+    let result = send(&mut svm, &[approve], &[&owner]);
+    assert_custom_error(result, ErrorCode::ProgramAlreadyApproved);
+
+    let revoke = remove_approved_program_instruction(&pubkey(&owner), &program_id);
+    send(&mut svm, std::slice::from_ref(&revoke), &[&owner]).expect("remove_approved_program");
+    let config: Config = load(&svm, &config_pda());
+    assert!(config.approved_programs.is_empty());
+    svm.expire_blockhash();
+    let result = send(&mut svm, &[revoke], &[&owner]);
+    assert_custom_error(result, ErrorCode::ProgramNotApproved);
+}
+
+#[test]
+fn pause_halts_transfers_except_between_treasuries() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    let treasury_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    send(
+        &mut svm,
+        &[
+            add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1)),
+            add_treasury_instruction(&pubkey(&owner), &sender_ata),
+            add_treasury_instruction(&pubkey(&owner), &treasury_ata),
+            pause_instruction(&pubkey(&owner)),
+        ],
+        &[&owner],
+    )
+    .expect("pause");
+    let config: Config = load(&svm, &config_pda());
+    assert!(config.paused);
+    assert_eq!(config.treasuries, vec![sender_ata, treasury_ata]);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+    assert_custom_error(result, ErrorCode::TransfersPaused);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &treasury_ata);
+    send(&mut svm, &[transfer], &[&sender]).expect("treasury transfer while paused");
+    assert_eq!(balance(&svm, &treasury_ata), TRANSFER_AMOUNT);
+
+    send(&mut svm, &[unpause_instruction(&pubkey(&owner))], &[&owner]).expect("unpause");
+    svm.expire_blockhash();
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    send(&mut svm, &[transfer], &[&sender]).expect("transfer after unpause");
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+}
+
+#[test]
+fn operator_pauses_only_owner_unpauses() {
+    let (mut svm, owner) = setup();
+    setup_mint(&mut svm, &owner);
+    let operator = Keypair::new();
+    svm.airdrop(&operator.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(
+        &mut svm,
+        &[add_operator_ix(&owner, &pubkey(&operator))],
+        &[&owner],
+    )
+    .expect("add_operator");
+
+    send(
+        &mut svm,
+        &[pause_instruction(&pubkey(&operator))],
+        &[&operator],
+    )
+    .expect("pause");
+    let result = send(
+        &mut svm,
+        &[unpause_instruction(&pubkey(&operator))],
+        &[&operator],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    let result = send(
+        &mut svm,
+        &[add_treasury_instruction(
+            &pubkey(&operator),
+            &Pubkey::new_unique(),
+        )],
+        &[&operator],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    send(&mut svm, &[unpause_instruction(&pubkey(&owner))], &[&owner]).expect("unpause");
+    let config: Config = load(&svm, &config_pda());
+    assert!(!config.paused);
+}
+
+#[test]
+fn expiring_mode_entries_expire_after_the_entry_duration() {
+    let (mut svm, owner) = setup();
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let user = Pubkey::new_unique();
+
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &user, kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    assert!(entry.is_active);
+    assert_eq!(entry.expiry_timestamp, now + Config::ENTRY_DURATION);
+
+    let result = send(&mut svm, &[expire_user_ix(&user)], &[&owner]);
+    assert_custom_error(result, ErrorCode::NotExpired);
+
+    warp_to(&mut svm, entry.expiry_timestamp);
+    send(&mut svm, &[expire_user_ix(&user)], &[&owner]).expect("expire_user");
+
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    assert!(!entry.is_active);
+}
+
+#[test]
+fn batch_add_is_unsupported_in_expiring_mode() {
+    let (mut svm, owner) = setup();
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let users = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    // A batch cannot give each entry its own expiry or queue its tasks
+    let result = send(
+        &mut svm,
+        &[batch_add_instruction(&pubkey(&owner), &users, &kyc(1))],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::ExpiringModeUnsupported);
+    for user in &users {
+        assert!(svm
+            .get_account(&address(&whitelisted_user_pda(user)))
+            .is_none());
+    }
+}
+
+#[test]
+fn self_register_is_unsupported_in_expiring_mode() {
+    let (mut svm, owner) = setup();
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let provider = Keypair::new();
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(
+        &mut svm,
+        &[set_kyc_provider_instruction(
+            &pubkey(&owner),
+            Some(pubkey(&provider)),
+        )],
+        &[&owner],
+    )
+    .expect("set_kyc_provider");
+
+    // The attestation carries no entry expiry, and the user cannot queue the expiry tasks
+    let attestation = attestation(&svm, &user, 1, 3600);
+    let result = send(
+        &mut svm,
+        &self_register_ix(&provider, &attestation),
+        &[&user],
+    );
+    assert_custom_error(result, ErrorCode::ExpiringModeUnsupported);
+    assert!(svm
+        .get_account(&address(&whitelisted_user_pda(&pubkey(&user))))
+        .is_none());
+}
+
+#[test]
+fn hook_rejects_expired_entries_before_expire_user_runs() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    let sender_key = pubkey(&sender);
+
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &sender_key, kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&sender_key));
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+
+    warp_to(&mut svm, entry.expiry_timestamp - 1);
+    send(&mut svm, std::slice::from_ref(&transfer), &[&sender]).expect("transfer before expiry");
+
+    // Still active, but past its expiry: the hook does not wait for `expire_user`
+    warp_to(&mut svm, entry.expiry_timestamp);
+    let result = send(&mut svm, std::slice::from_ref(&transfer), &[&sender]);
+    assert_custom_error(result, ErrorCode::WhitelistEntryExpired);
+
+    send(&mut svm, &[expire_user_ix(&sender_key)], &[&owner]).expect("expire_user");
+    svm.expire_blockhash();
+    let result = send(&mut svm, &[transfer], &[&sender]);
+    assert_custom_error(result, ErrorCode::WhitelistEntryExpired);
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+}
+
+#[test]
+fn renew_whitelist_extends_and_reactivates_entries() {
+    let (mut svm, owner) = setup();
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let user = Pubkey::new_unique();
+    send(
+        &mut svm,
+        &[set_max_entry_duration_ix(&owner, HOUR)],
+        &[&owner],
+    )
+    .expect("set_max_entry_duration");
+
+    let result = send(
+        &mut svm,
+        &[add_expiring_entry_ix(
+            &owner,
+            &user,
+            kyc(1),
+            Some(EntryExpiry::Duration(2 * HOUR)),
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidExpiry);
+
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    send(
+        &mut svm,
+        &[add_expiring_entry_ix(
+            &owner,
+            &user,
+            kyc(1),
+            Some(EntryExpiry::At(now + 600)),
+        )],
+        &[&owner],
+    )
+    .expect("add_to_whitelist with expiry");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    assert_eq!(entry.expiry_timestamp, now + 600);
+
+    warp_to(&mut svm, now + 600);
+    send(&mut svm, &[expire_user_ix(&user)], &[&owner]).expect("expire_user");
+
+    send(
+        &mut svm,
+        &[renew_whitelist_ix(
+            &owner,
+            &user,
+            EntryExpiry::Duration(1200),
+        )],
+        &[&owner],
+    )
+    .expect("renew_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    assert!(entry.is_active);
+    assert_eq!(entry.expiry_timestamp, now + 600 + 1200);
+
+    // A task queued for the old expiry cannot deactivate the renewed entry
+    svm.expire_blockhash();
+    let result = send(&mut svm, &[expire_user_ix(&user)], &[&owner]);
+    assert_custom_error(result, ErrorCode::NotExpired);
+
+    // Renewing only ever moves the expiry later
+    let result = send(
+        &mut svm,
+        &[renew_whitelist_ix(
+            &owner,
+            &user,
+            EntryExpiry::Duration(600),
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidExpiry);
+}
+
+#[test]
+fn add_to_whitelist_queues_a_crank_callable_expiry_task() {
+    let (mut svm, owner) = setup();
+    add_tuktuk_program(&mut svm);
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let task_queue = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    send(
+        &mut svm,
+        &[set_task_queue_instruction(
+            &pubkey(&owner),
+            Some(task_queue),
+        )],
+        &[&owner],
+    )
+    .expect("set_task_queue");
+
+    // With a task queue set, entries cannot be added without their expiry task
+    let result = send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &user, kyc(1))],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::MissingExpiryTask);
+    let result = send(
+        &mut svm,
+        &[add_scheduled_entry_ix(
+            &owner,
+            &user,
+            &Pubkey::new_unique(),
+            0,
+            None,
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::WrongTaskQueue);
+
+    send(
+        &mut svm,
+        &[add_scheduled_entry_ix(&owner, &user, &task_queue, 0, None)],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    let task: TaskV0 = load(&svm, &task_pda(&task_queue, 0));
+    assert_eq!(task.task_queue, task_queue);
+    assert_eq!(task.trigger, TriggerV0::Timestamp(entry.expiry_timestamp));
+    assert_eq!(task.rent_refund, pubkey(&owner));
+
+    let crank_turner = Keypair::new();
+    svm.airdrop(&crank_turner.pubkey(), LAMPORTS_PER_SOL)
+        .unwrap();
+    assert!(crank(&mut svm, &crank_turner).is_empty(), "not due yet");
+
+    // Renewing re-queues the expiry under a new task id, so it cannot skip the tasks
+    let result = send(
+        &mut svm,
+        &[renew_whitelist_ix(
+            &owner,
+            &user,
+            EntryExpiry::Duration(600),
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::MissingExpiryTask);
+    send(
+        &mut svm,
+        &[renew_scheduled_ix(
+            &owner,
+            &user,
+            EntryExpiry::Duration(600),
+            &task_queue,
+            1,
+        )],
+        &[&owner],
+    )
+    .expect("renew_whitelist");
+    let renewed: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    let task: TaskV0 = load(&svm, &task_pda(&task_queue, 1));
+    assert_eq!(task.trigger, TriggerV0::Timestamp(renewed.expiry_timestamp));
+    assert_eq!(queued_tasks(&svm).len(), 2);
+
+    // The first task fires at the old expiry and cannot cut off the renewed entry
+    warp_to(&mut svm, entry.expiry_timestamp);
+    let mut results = crank(&mut svm, &crank_turner);
+    assert_eq!(results.len(), 1);
+    assert_custom_error(results.remove(0).1, ErrorCode::NotExpired);
+    assert!(load::<WhitelistedUser>(&svm, &whitelisted_user_pda(&user)).is_active);
+
+    // At the new expiry the crank runs both without the admin's signature
+    warp_to(&mut svm, renewed.expiry_timestamp);
+    for (task, result) in crank(&mut svm, &crank_turner) {
+        result.unwrap_or_else(|failed| panic!("task {task}: {:?}", failed.err));
+    }
+    assert!(!load::<WhitelistedUser>(&svm, &whitelisted_user_pda(&user)).is_active);
+    assert!(queued_tasks(&svm).is_empty());
+}
+
+#[test]
+fn grace_period_lets_expired_entries_send_but_not_receive() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let (seller, seller_ata) = funded_holder(&mut svm, &owner, &mint);
+    let (buyer, buyer_ata) = funded_holder(&mut svm, &owner, &mint);
+    send(
+        &mut svm,
+        &[
+            set_expiry_grace_instruction(&pubkey(&owner), HOUR, 0),
+            add_to_whitelist_ix(&owner, &pubkey(&seller), kyc(1)),
+            add_expiring_entry_ix(
+                &owner,
+                &pubkey(&buyer),
+                kyc(1),
+                Some(EntryExpiry::Duration(4 * HOUR)),
+            ),
+        ],
+        &[&owner],
+    )
+    .expect("set_expiry_grace and add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&pubkey(&seller)));
+    let sell = transfer_ix(&svm, &seller, &mint, &seller_ata, &buyer_ata);
+    let buy = transfer_ix(&svm, &buyer, &mint, &buyer_ata, &seller_ata);
+
+    // Expired and deactivated, but still within the grace period: the seller can sell...
+    warp_to(&mut svm, entry.expiry_timestamp);
+    send(&mut svm, &[expire_user_ix(&pubkey(&seller))], &[&owner]).expect("expire_user");
+    send(&mut svm, std::slice::from_ref(&sell), &[&seller]).expect("sell during grace");
+    // ...but not receive
+    let result = send(&mut svm, std::slice::from_ref(&buy), &[&buyer]);
+    assert_custom_error(result, ErrorCode::EntryInGracePeriod);
+
+    // Past the grace period the entry can do neither
+    warp_to(&mut svm, entry.expiry_timestamp + HOUR);
+    let result = send(&mut svm, &[sell], &[&seller]);
+    assert_custom_error(result, ErrorCode::WhitelistEntryExpired);
+    let result = send(&mut svm, &[buy], &[&buyer]);
+    assert_custom_error(result, ErrorCode::WhitelistEntryExpired);
+    assert_eq!(balance(&svm, &buyer_ata), MINT_AMOUNT + TRANSFER_AMOUNT);
+}
+
+#[test]
+fn expiry_notice_task_warns_before_the_entry_expires() {
+    let (mut svm, owner) = setup();
+    add_tuktuk_program(&mut svm);
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let task_queue = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    send(
+        &mut svm,
+        &[
+            set_task_queue_instruction(&pubkey(&owner), Some(task_queue)),
+            set_expiry_grace_instruction(&pubkey(&owner), 0, 30),
+        ],
+        &[&owner],
+    )
+    .expect("set_task_queue and set_expiry_grace");
+
+    // With an expiry notice set, the notice task is required too
+    let result = send(
+        &mut svm,
+        &[add_scheduled_entry_ix(&owner, &user, &task_queue, 0, None)],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::MissingExpiryTask);
+
+    send(
+        &mut svm,
+        &[add_scheduled_entry_ix(
+            &owner,
+            &user,
+            &task_queue,
+            0,
+            Some(1),
+        )],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    let notice: TaskV0 = load(&svm, &task_pda(&task_queue, 1));
+    assert_eq!(
+        notice.trigger,
+        TriggerV0::Timestamp(entry.expiry_timestamp - 30)
+    );
+
+    let result = send(&mut svm, &[notify_expiring_instruction(&user)], &[&owner]);
+    assert_custom_error(result, ErrorCode::NotExpiringSoon);
+
+    let crank_turner = Keypair::new();
+    svm.airdrop(&crank_turner.pubkey(), LAMPORTS_PER_SOL)
+        .unwrap();
+    warp_to(&mut svm, entry.expiry_timestamp - 30);
+    let mut results = crank(&mut svm, &crank_turner);
+    assert_eq!(results.len(), 1, "only the notice is due");
+    let (task, result) = results.remove(0);
+    assert_eq!(task, task_pda(&task_queue, 1));
+    let meta = result.unwrap_or_else(|failed| panic!("notice: {:?}", failed.err));
+    assert!(meta
+        .logs
+        .iter()
+        .any(|log| log.contains("Entry expiring soon")));
+    assert!(load::<WhitelistedUser>(&svm, &whitelisted_user_pda(&user)).is_active);
+
+    warp_to(&mut svm, entry.expiry_timestamp);
+    for (task, result) in crank(&mut svm, &crank_turner) {
+        result.unwrap_or_else(|failed| panic!("task {task}: {:?}", failed.err));
+    }
+    assert!(!load::<WhitelistedUser>(&svm, &whitelisted_user_pda(&user)).is_active);
+    assert!(queued_tasks(&svm).is_empty());
+}
+
+#[test]
+fn reap_expired_refunds_the_rent_payer_after_the_retention_period() {
+    let (mut svm, owner) = setup();
+    setup_mint_with_mode(&mut svm, &owner, WhitelistMode::Expiring);
+    let user = Pubkey::new_unique();
+    let bounty = 10_000;
+    send(
+        &mut svm,
+        &[
+            set_retention_instruction(&pubkey(&owner), HOUR, bounty),
+            add_to_whitelist_ix(&owner, &user, kyc(1)),
+        ],
+        &[&owner],
+    )
+    .expect("set_retention and add_to_whitelist");
+    let entry: WhitelistedUser = load(&svm, &whitelisted_user_pda(&user));
+    assert_eq!(entry.rent_payer, pubkey(&owner));
+    let rent = svm.minimum_balance_for_rent_exemption(WhitelistedUser::LEN);
+
+    let reaper = Keypair::new();
+    svm.airdrop(&reaper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let reap = reap_expired_instruction(&pubkey(&reaper), &user, &pubkey(&owner));
+
+    warp_to(&mut svm, entry.expiry_timestamp + HOUR - 1);
+    let result = send(&mut svm, std::slice::from_ref(&reap), &[&reaper]);
+    assert_custom_error(result, ErrorCode::RetentionNotElapsed);
+
+    warp_to(&mut svm, entry.expiry_timestamp + HOUR);
+    let result = send(
+        &mut svm,
+        &[reap_expired_instruction(
+            &pubkey(&reaper),
+            &user,
+            &pubkey(&reaper),
+        )],
+        &[&reaper],
+    );
+    assert_custom_error(result, ErrorCode::InvalidRentPayer);
+
+    let owner_balance = svm.get_balance(&owner.pubkey()).unwrap();
+    let reaper_balance = svm.get_balance(&reaper.pubkey()).unwrap();
+    send(&mut svm, &[reap], &[&reaper]).expect("reap_expired");
+
+    assert!(svm
+        .get_account(&address(&whitelisted_user_pda(&user)))
+        .is_none_or(|account| account.lamports == 0));
+    assert_eq!(
+        svm.get_balance(&owner.pubkey()).unwrap(),
+        owner_balance + rent - bounty
+    );
+    assert_eq!(
+        svm.get_balance(&reaper.pubkey()).unwrap(),
+        reaper_balance + bounty - 5_000
+    );
+}
+
+#[test]
+fn only_the_owner_or_mint_authority_initializes_a_vault() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let stranger = Keypair::new();
+    svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // Otherwise a stranger could take the hook's vault and decide who may hold the mint
+    let result = send(
+        &mut svm,
+        &[initialize_vault_ix(&stranger, &mint, HOOK_VAULT_ID)],
+        &[&stranger],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    assert!(svm
+        .get_account(&address(&vault_pda(&mint, HOOK_VAULT_ID)))
+        .is_none());
+
+    send(
+        &mut svm,
+        &[initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID)],
+        &[&owner],
+    )
+    .expect("initialize_vault");
+    let vault: Vault = load(&svm, &vault_pda(&mint, HOOK_VAULT_ID));
+    assert_eq!(vault.admin, pubkey(&owner));
+}
+
+#[test]
+fn vault_gated_mode_only_allows_whitelisted_vault_transfers() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let (outsider, outsider_ata) = funded_holder(&mut svm, &owner, &mint);
+
+    send(
+        &mut svm,
+        &[
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&sender), true),
+        ],
+        &[&owner],
+    )
+    .expect("vault setup");
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!(vault.mint, mint);
+    assert_eq!(vault.token_account, vault_token_account_pda(&vault_key));
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&sender)));
+    assert_eq!(position.user, pubkey(&sender));
+    assert_eq!(position.shares, 0);
+
+    // Whitelisted users can move tokens into the vault, but not to each other
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &outsider_ata);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+    assert_custom_error(result, ErrorCode::TransferMustTouchVault);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &vault.token_account);
+    send(&mut svm, &[transfer], &[&sender]).expect("deposit into vault");
+    assert_eq!(balance(&svm, &vault.token_account), TRANSFER_AMOUNT);
+
+    let transfer = transfer_ix(&svm, &outsider, &mint, &outsider_ata, &vault.token_account);
+    let result = send(&mut svm, &[transfer], &[&outsider]);
+    assert_custom_error(result, ErrorCode::SourceNotWhitelisted);
+
+    // Removing the user closes their position and the vault to them
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vault_key,
+            &pubkey(&sender),
+            false,
+        )],
+        &[&owner],
+    )
+    .expect("remove_from_vault_whitelist");
+    assert!(svm
+        .get_account(&address(&vault_position_pda(&vault_key, &pubkey(&sender))))
+        .is_none_or(|account| account.lamports == 0));
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &vault.token_account);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+    assert_custom_error(result, ErrorCode::SourceNotWhitelisted);
+}
+
+#[test]
+fn migrate_vault_whitelist_moves_legacy_entries_into_positions() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let (legacy, legacy_ata) = funded_holder(&mut svm, &owner, &mint);
+    let (migrated, _) = funded_holder(&mut svm, &owner, &mint);
+    send(
+        &mut svm,
+        &[
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID + 1),
+        ],
+        &[&owner],
+    )
+    .expect("initialize_vault");
+    set_legacy_vault_whitelist(
+        &mut svm,
+        &[
+            WhitelistEntry {
+                user: pubkey(&migrated),
+                amount: 5,
+            },
+            WhitelistEntry {
+                user: pubkey(&legacy),
+                amount: 7,
+            },
+        ],
+    );
+
+    // Shares are only credited for legacy amounts the vault token account holds
+    let result = send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&owner),
+            &vault_key,
+            &[pubkey(&migrated)],
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::VaultUndercollateralized);
+    svm.expire_blockhash();
+
+    // Until they are migrated, users on the legacy list can still use the vault
+    let deposit = transfer_ix(
+        &svm,
+        &legacy,
+        &mint,
+        &legacy_ata,
+        &vault_token_account_pda(&vault_key),
+    );
+    send(&mut svm, &[deposit], &[&legacy]).expect("deposit by legacy entry");
+
+    let outsider = Keypair::new();
+    svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let result = send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&outsider),
+            &vault_key,
+            &[pubkey(&migrated)],
+        )],
+        &[&outsider],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    // The legacy list gated the hook's vault, so no other vault can take its entries
+    let other_vault = vault_pda(&mint, HOOK_VAULT_ID + 1);
+    let result = send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&owner),
+            &other_vault,
+            &[pubkey(&migrated)],
+        )],
+        &[&owner],
+    );
+    assert!(result.is_err());
+    let result = send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&owner),
+            &vault_key,
+            &[pubkey(&legacy)],
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InvalidPositionAccount);
+
+    send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&owner),
+            &vault_key,
+            &[pubkey(&migrated)],
+        )],
+        &[&owner],
+    )
+    .expect("migrate first entry");
+    let whitelist: Whitelist = load(&svm, &vault_whitelist_pda());
+    assert_eq!(whitelist.address.len(), 1);
+    assert_eq!(
+        svm.get_account(&address(&vault_whitelist_pda()))
+            .unwrap()
+            .data
+            .len(),
+        8 + 4 + WhitelistEntry::SIZE + 1
+    );
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&migrated)));
+    assert_eq!((position.user, position.shares), (pubkey(&migrated), 5));
+
+    // A position created since the migration started keeps its amount
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vault_key,
+            &pubkey(&legacy),
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("add_to_vault_whitelist");
+    send(
+        &mut svm,
+        &[migrate_vault_whitelist_instruction(
+            &pubkey(&owner),
+            &vault_key,
+            &[pubkey(&legacy)],
+        )],
+        &[&owner],
+    )
+    .expect("migrate last entry");
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&legacy)));
+    assert_eq!(position.shares, 7);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!((vault.total_assets, vault.total_shares), (12, 12));
+    assert!(svm
+        .get_account(&address(&vault_whitelist_pda()))
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn vault_authority_signs_withdrawals_and_only_the_admin_manages_the_vault() {
+    let (mut svm, owner) = setup();
+    send(&mut svm, &[init_config_ix(&owner)], &[&owner]).expect("init_config");
+    // A mint without the hook, so the vault's own Token 2022 CPIs do not re-enter this program
+    let mint = vault_authority_mint(&mut svm);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let outsider = Keypair::new();
+    svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let owner_ata = token_account(&mut svm, &owner, &owner, &mint);
+    let outsider_ata = token_account(&mut svm, &owner, &outsider, &mint);
+    send(
+        &mut svm,
+        &[initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID)],
+        &[&owner],
+    )
+    .expect("initialize_vault");
+
+    let result = send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &outsider,
+            &vault_key,
+            &pubkey(&outsider),
+            true,
+        )],
+        &[&outsider],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vault_key,
+            &pubkey(&owner),
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("add_to_vault_whitelist");
+    let result = send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &outsider,
+            &vault_key,
+            &pubkey(&owner),
+            false,
+        )],
+        &[&outsider],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    let result = send(
+        &mut svm,
+        &[mint_token_ix(&outsider, &mint, &outsider_ata)],
+        &[&outsider],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    send(
+        &mut svm,
+        &[mint_token_ix(&owner, &mint, &owner_ata)],
+        &[&owner],
+    )
+    .expect("mint_token");
+    assert_eq!(balance(&svm, &owner_ata), MINT_AMOUNT);
+
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            MINT_AMOUNT,
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("deposit");
+    let result = send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            MINT_AMOUNT + 1,
+            false,
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::InsufficientWhitelistedBalance);
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+            false,
+        )],
+        &[&owner],
+    )
+    .expect("withdraw");
+    assert_eq!(balance(&svm, &owner_ata), TRANSFER_AMOUNT);
+    assert_eq!(
+        balance(&svm, &vault_token_account_pda(&vault_key)),
+        MINT_AMOUNT - TRANSFER_AMOUNT
+    );
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&owner)));
+    assert_eq!(position.shares, MINT_AMOUNT - TRANSFER_AMOUNT);
+
+    // Without a position of their own, others cannot withdraw the deposited tokens
+    let result = send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &outsider,
+            &mint,
+            HOOK_VAULT_ID,
+            &outsider_ata,
+            1,
+            false,
+        )],
+        &[&outsider],
+    );
+    assert!(result.is_err());
+    assert_eq!(balance(&svm, &outsider_ata), 0);
+}
+
+#[test]
+fn hook_mint_vault_burns_and_mints_instead_of_transferring() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let vault_token_account = vault_token_account_pda(&vault_key);
+    let (investor, investor_ata) = funded_holder(&mut svm, &owner, &mint);
+    send(
+        &mut svm,
+        &[
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&investor), true),
+        ],
+        &[&owner],
+    )
+    .expect("vault setup");
+
+    // A Token 2022 transfer from the program would re-enter its own hook
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &investor,
+            &mint,
+            HOOK_VAULT_ID,
+            &investor_ata,
+            TRANSFER_AMOUNT,
+            true,
+        )],
+        &[&investor],
+    )
+    .expect("deposit");
+    assert_eq!(balance(&svm, &investor_ata), MINT_AMOUNT - TRANSFER_AMOUNT);
+    assert_eq!(balance(&svm, &vault_token_account), TRANSFER_AMOUNT);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!(
+        (vault.total_assets, vault.total_shares),
+        (TRANSFER_AMOUNT, TRANSFER_AMOUNT)
+    );
+
+    // Burning and minting skips the hook, so the vault enforces the pause itself
+    send(&mut svm, &[pause_instruction(&pubkey(&owner))], &[&owner]).expect("pause");
+    let withdraw = vault_transfer_ix(
+        &investor,
+        &mint,
+        HOOK_VAULT_ID,
+        &investor_ata,
+        TRANSFER_AMOUNT,
+        false,
+    );
+    let result = send(&mut svm, std::slice::from_ref(&withdraw), &[&investor]);
+    assert_custom_error(result, ErrorCode::TransfersPaused);
+    send(&mut svm, &[unpause_instruction(&pubkey(&owner))], &[&owner]).expect("unpause");
+    svm.expire_blockhash();
+
+    send(&mut svm, &[withdraw], &[&investor]).expect("withdraw");
+    assert_eq!(balance(&svm, &investor_ata), MINT_AMOUNT);
+    assert_eq!(balance(&svm, &vault_token_account), 0);
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&investor)));
+    assert_eq!(position.shares, 0);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!((vault.total_assets, vault.total_shares), (0, 0));
+}
+
+#[test]
+fn each_mint_and_vault_id_gets_its_own_vault() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let other_mint = vault_authority_mint(&mut svm);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let owner_ata = token_account(&mut svm, &owner, &owner, &other_mint);
+    send(
+        &mut svm,
+        &[
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            initialize_vault_ix(&owner, &other_mint, HOOK_VAULT_ID),
+            initialize_vault_ix(&owner, &other_mint, 1),
+        ],
+        &[&owner],
+    )
+    .expect("initialize vaults");
+
+    let vaults = [
+        vault_pda(&mint, HOOK_VAULT_ID),
+        vault_pda(&other_mint, HOOK_VAULT_ID),
+        vault_pda(&other_mint, 1),
+    ];
+    for (vault_key, (vault_mint, vault_id)) in vaults.iter().zip([
+        (mint, HOOK_VAULT_ID),
+        (other_mint, HOOK_VAULT_ID),
+        (other_mint, 1),
+    ]) {
+        let vault: Vault = load(&svm, vault_key);
+        assert_eq!((vault.mint, vault.vault_id), (vault_mint, vault_id));
+        assert_eq!(vault.token_account, vault_token_account_pda(vault_key));
+    }
+    svm.expire_blockhash();
+    let result = send(
+        &mut svm,
+        &[initialize_vault_ix(&owner, &other_mint, 1)],
+        &[&owner],
+    );
+    assert!(result.is_err());
+
+    // The hook derives the vault from the transferred mint, so a position in another
+    // mint's vault does not count
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vaults[1],
+            &pubkey(&sender),
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("add to other mint's vault");
+    let deposit = transfer_ix(
+        &svm,
+        &sender,
+        &mint,
+        &sender_ata,
+        &vault_token_account_pda(&vaults[0]),
+    );
+    let result = send(&mut svm, std::slice::from_ref(&deposit), &[&sender]);
+    assert_custom_error(result, ErrorCode::SourceNotWhitelisted);
+
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vaults[0],
+            &pubkey(&sender),
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("add to the mint's vault");
+    svm.expire_blockhash();
+    send(&mut svm, &[deposit], &[&sender]).expect("deposit into the mint's vault");
+    assert_eq!(
+        balance(&svm, &vault_token_account_pda(&vaults[0])),
+        TRANSFER_AMOUNT
+    );
+
+    // Vaults of the same mint keep separate positions and balances
+    send(
+        &mut svm,
+        &[
+            vault_whitelist_ix(&owner, &vaults[2], &pubkey(&owner), true),
+            mint_token_ix(&owner, &other_mint, &owner_ata),
+            vault_transfer_ix(&owner, &other_mint, 1, &owner_ata, MINT_AMOUNT, true),
+        ],
+        &[&owner],
+    )
+    .expect("deposit into vault 1");
+    assert_eq!(
+        balance(&svm, &vault_token_account_pda(&vaults[2])),
+        MINT_AMOUNT
+    );
+    assert_eq!(balance(&svm, &vault_token_account_pda(&vaults[1])), 0);
+    let result = send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &other_mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            1,
+            false,
+        )],
+        &[&owner],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn distributed_rewards_raise_the_share_price_for_existing_holders() {
+    let (mut svm, owner) = setup();
+    let mint = vault_authority_mint(&mut svm);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let investor = Keypair::new();
+    svm.airdrop(&investor.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let owner_ata = token_account(&mut svm, &owner, &owner, &mint);
+    let investor_ata = token_account(&mut svm, &owner, &investor, &mint);
+    let fund_investor = token_transfer_checked(
+        &TOKEN_2022_PROGRAM_ID,
+        &owner_ata,
+        &mint,
+        &investor_ata,
+        &pubkey(&owner),
+        &[],
+        TRANSFER_AMOUNT,
+        DECIMALS,
+    )
+    .unwrap();
+    send(
+        &mut svm,
+        &[
+            init_config_ix(&owner),
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&owner), true),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&investor), true),
+            mint_token_ix(&owner, &mint, &owner_ata),
+            fund_investor,
+        ],
+        &[&owner],
+    )
+    .expect("vault setup");
+
+    let result = send(
+        &mut svm,
+        &[distribute_rewards_ix(
+            &owner,
+            &mint,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::NoVaultShares);
+
+    // Both deposit at one share per token
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+            true,
+        )],
+        &[&owner],
+    )
+    .expect("owner deposit");
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &investor,
+            &mint,
+            HOOK_VAULT_ID,
+            &investor_ata,
+            TRANSFER_AMOUNT,
+            true,
+        )],
+        &[&investor],
+    )
+    .expect("investor deposit");
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&investor)));
+    assert_eq!(position.shares, TRANSFER_AMOUNT);
+
+    let result = send(
+        &mut svm,
+        &[distribute_rewards_ix(&investor, &mint, &investor_ata, 1)],
+        &[&investor],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    svm.expire_blockhash();
+    send(
+        &mut svm,
+        &[distribute_rewards_ix(
+            &owner,
+            &mint,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+        )],
+        &[&owner],
+    )
+    .expect("distribute_rewards");
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!(
+        (vault.total_assets, vault.total_shares),
+        (3 * TRANSFER_AMOUNT, 2 * TRANSFER_AMOUNT)
+    );
+
+    // One base unit is now worth 2/3 of a share, which rounds down to nothing
+    let result = send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            1,
+            true,
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::ZeroShares);
+    let result = send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vault_key,
+            &pubkey(&investor),
+            false,
+        )],
+        &[&owner],
+    );
+    assert_custom_error(result, ErrorCode::PositionNotEmpty);
+
+    // Each holder redeems half of the rewards with their shares
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &investor,
+            &mint,
+            HOOK_VAULT_ID,
+            &investor_ata,
+            TRANSFER_AMOUNT,
+            false,
+        )],
+        &[&investor],
+    )
+    .expect("investor withdraw");
+    assert_eq!(balance(&svm, &investor_ata), 3 * TRANSFER_AMOUNT / 2);
+    svm.expire_blockhash();
+    send(
+        &mut svm,
+        &[vault_whitelist_ix(
+            &owner,
+            &vault_key,
+            &pubkey(&investor),
+            false,
+        )],
+        &[&owner],
+    )
+    .expect("remove emptied position");
+
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &owner,
+            &mint,
+            HOOK_VAULT_ID,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+            false,
+        )],
+        &[&owner],
+    )
+    .expect("owner withdraw");
+    assert_eq!(
+        balance(&svm, &owner_ata),
+        MINT_AMOUNT - 3 * TRANSFER_AMOUNT / 2
+    );
+    assert_eq!(balance(&svm, &vault_token_account_pda(&vault_key)), 0);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!((vault.total_assets, vault.total_shares), (0, 0));
+}
+
+#[test]
+fn hook_mint_rewards_and_deposits_are_priced_in_shares() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint_with_mode(&mut svm, &owner, WhitelistMode::VaultGated);
+    let vault_key = vault_pda(&mint, HOOK_VAULT_ID);
+    let (early, early_ata) = funded_holder(&mut svm, &owner, &mint);
+    let (late, late_ata) = funded_holder(&mut svm, &owner, &mint);
+    let owner_ata = token_account(&mut svm, &owner, &owner, &mint);
+    send(
+        &mut svm,
+        &[
+            issue_tokens_ix(&owner, &mint, &owner_ata),
+            initialize_vault_ix(&owner, &mint, HOOK_VAULT_ID),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&early), true),
+            vault_whitelist_ix(&owner, &vault_key, &pubkey(&late), true),
+        ],
+        &[&owner],
+    )
+    .expect("vault setup");
+    let deposit = |user: &Keypair, ata: &Pubkey| {
+        vault_transfer_ix(user, &mint, HOOK_VAULT_ID, ata, TRANSFER_AMOUNT, true)
+    };
+
+    send(&mut svm, &[deposit(&early, &early_ata)], &[&early]).expect("early deposit");
+    send(
+        &mut svm,
+        &[distribute_rewards_ix(
+            &owner,
+            &mint,
+            &owner_ata,
+            TRANSFER_AMOUNT,
+        )],
+        &[&owner],
+    )
+    .expect("distribute_rewards");
+    assert_eq!(balance(&svm, &owner_ata), MINT_AMOUNT - TRANSFER_AMOUNT);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!(
+        (vault.total_assets, vault.total_shares),
+        (2 * TRANSFER_AMOUNT, TRANSFER_AMOUNT)
+    );
+
+    // Each share is now worth two tokens
+    send(&mut svm, &[deposit(&late, &late_ata)], &[&late]).expect("late deposit");
+    let position: VaultPosition = load(&svm, &vault_position_pda(&vault_key, &pubkey(&late)));
+    assert_eq!(position.shares, TRANSFER_AMOUNT / 2);
+    assert_eq!(
+        balance(&svm, &vault_token_account_pda(&vault_key)),
+        3 * TRANSFER_AMOUNT
+    );
+
+    // Only the early depositor earned the rewards
+    send(
+        &mut svm,
+        &[vault_transfer_ix(
+            &early,
+            &mint,
+            HOOK_VAULT_ID,
+            &early_ata,
+            TRANSFER_AMOUNT,
+            false,
+        )],
+        &[&early],
+    )
+    .expect("early withdraw");
+    assert_eq!(balance(&svm, &early_ata), MINT_AMOUNT + TRANSFER_AMOUNT);
+    let vault: Vault = load(&svm, &vault_key);
+    assert_eq!(
+        (vault.total_assets, vault.total_shares),
+        (TRANSFER_AMOUNT, TRANSFER_AMOUNT / 2)
+    );
+}
+
+#[test]
+fn transfer_log_records_transfers_while_enabled() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient = Keypair::new();
+    let recipient_ata = token_account(&mut svm, &owner, &recipient, &mint);
+    let owner_key = pubkey(&owner);
+
+    send(
+        &mut svm,
+        &[
+            add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1)),
+            init_transfer_log_instruction(&owner_key, &mint),
+            set_transfer_log_instruction(&owner_key, &mint, true),
+        ],
+        &[&owner],
+    )
+    .expect("enable transfer log");
+    let config: Config = load(&svm, &config_pda());
+    assert_eq!(config.logged_mints, [mint]);
+
+    // Only logged mints write-lock their log in transfers
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    assert!(log_is_writable(&transfer, &mint));
+    send(&mut svm, &[transfer], &[&sender]).expect("logged transfer");
+
+    let clock = svm.get_sysvar::<Clock>();
+    let log = svm.get_account(&address(&transfer_log_pda(&mint))).unwrap();
+    let entries = decode_transfer_log(&log.data).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].source_owner, pubkey(&sender));
+    assert_eq!(entries[0].destination_owner, pubkey(&recipient));
+    assert_eq!(entries[0].amount, TRANSFER_AMOUNT);
+    assert_eq!(entries[0].slot, clock.slot);
+    assert_eq!(entries[0].timestamp, clock.unix_timestamp);
+
+    // Switched off, transfers go through without being recorded
+    send(
+        &mut svm,
+        &[set_transfer_log_instruction(&owner_key, &mint, false)],
+        &[&owner],
+    )
+    .expect("disable transfer log");
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    assert!(!log_is_writable(&transfer, &mint));
+    svm.expire_blockhash();
+    send(&mut svm, &[transfer], &[&sender]).expect("unlogged transfer");
+
+    let log = svm.get_account(&address(&transfer_log_pda(&mint))).unwrap();
+    assert_eq!(decode_transfer_log(&log.data).unwrap(), entries);
+    assert_eq!(balance(&svm, &recipient_ata), 2 * TRANSFER_AMOUNT);
+}
+
+#[test]
+fn trading_schedule_limits_transfers_to_market_hours() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    // Mondays 09:00 to 17:00 UTC, and no trading on the second Monday
+    let windows = vec![TradingWindow {
+        open: 9 * HOUR as u32,
+        close: 17 * HOUR as u32,
+    }];
+    let blackouts = vec![BlackoutPeriod {
+        start: MONDAY + 7 * 24 * HOUR,
+        end: MONDAY + 8 * 24 * HOUR,
+    }];
+
+    send(
+        &mut svm,
+        &[
+            add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1)),
+            init_trading_schedule_ix(&owner, windows, blackouts),
+            set_trading_schedule_ix(&owner, true),
+        ],
+        &[&owner],
+    )
+    .expect("enable trading schedule");
+    let config: Config = load(&svm, &config_pda());
+    assert_eq!(config.trading_schedule, Some(trading_schedule_pda()));
+    let schedule: TradingSchedule = load(&svm, &trading_schedule_pda());
+    assert_eq!(schedule.blackouts.len(), 1);
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    warp_to(&mut svm, MONDAY + 10 * HOUR);
+    send(&mut svm, std::slice::from_ref(&transfer), &[&sender]).expect("transfer in market hours");
+
+    warp_to(&mut svm, MONDAY + 18 * HOUR);
+    let result = send(&mut svm, std::slice::from_ref(&transfer), &[&sender]);
+    assert_custom_error(result, ErrorCode::OutsideTradingHours);
+
+    warp_to(&mut svm, MONDAY + 7 * 24 * HOUR + 10 * HOUR);
+    let result = send(&mut svm, std::slice::from_ref(&transfer), &[&sender]);
+    assert_custom_error(result, ErrorCode::TradingBlackout);
+
+    send(
+        &mut svm,
+        &[set_trading_schedule_ix(&owner, false)],
+        &[&owner],
+    )
+    .expect("disable trading schedule");
+    warp_to(&mut svm, MONDAY + 18 * HOUR);
+    send(&mut svm, &[transfer], &[&sender]).expect("transfer without schedule");
+    assert_eq!(balance(&svm, &recipient_ata), 2 * TRANSFER_AMOUNT);
+}
+
+#[test]
+fn hook_rejects_direct_calls() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+
+    // Calling the Execute entrypoint ourselves: the source account is not mid-transfer
+    let direct = AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::TransferHook {
+            source_token: sender_ata,
+            mint,
+            destination_token: recipient_ata,
+            owner: pubkey(&sender),
+            extra_account_meta_list: extra_account_meta_list_pda(&mint),
+            whitelisted_user: whitelisted_user_pda(&pubkey(&sender)),
+            config: config_pda(),
+            instructions_sysvar: sysvar::instructions::ID,
+            destination_whitelisted_user: whitelisted_user_pda(&Pubkey::default()),
+            vault_whitelist: vault_whitelist_pda(),
+            vault: vault_pda(&mint, HOOK_VAULT_ID),
+            transfer_log: transfer_log_pda(&mint),
+            trading_schedule: trading_schedule_pda(),
+            source_vault_position: vault_position_pda(
+                &vault_pda(&mint, HOOK_VAULT_ID),
+                &pubkey(&sender),
+            ),
+            destination_vault_position: vault_position_pda(
+                &vault_pda(&mint, HOOK_VAULT_ID),
+                &Pubkey::default(),
+            ),
+            source_merkle_claim: merkle_claim_pda(&pubkey(&sender)),
+            destination_merkle_claim: merkle_claim_pda(&Pubkey::default()),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::TransferToken {
+            amount: TRANSFER_AMOUNT,
+        }
+        .data(),
+    };
+
+    assert!(send(&mut svm, &[direct], &[&sender]).is_err());
+    assert_eq!(balance(&svm, &sender_ata), MINT_AMOUNT);
+}
+
+#[test]
+fn removed_owner_cannot_transfer() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    let sender_key = pubkey(&sender);
+
+    send(
+        &mut svm,
+        &[add_to_whitelist_ix(&owner, &sender_key, kyc(1))],
+        &[&owner],
+    )
+    .expect("add_to_whitelist");
+    send(
+        &mut svm,
+        &[remove_from_whitelist_ix(
+            &owner,
+            &sender_key,
+            &pubkey(&owner),
+        )],
+        &[&owner],
+    )
+    .expect("remove_from_whitelist");
+    assert!(svm
+        .get_account(&address(&whitelisted_user_pda(&sender_key)))
+        .is_none_or(|account| account.lamports == 0));
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    let result = send(&mut svm, &[transfer], &[&sender]);
+
+    assert_custom_error(result, ErrorCode::NotWhiteListed);
+    assert_eq!(balance(&svm, &sender_ata), MINT_AMOUNT);
+}
+
+#[test]
+fn merkle_members_transfer_under_the_current_root_only() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    let sender_key = pubkey(&sender);
+
+    let tree = MerkleTree::new(&[sender_key, Pubkey::new_unique(), Pubkey::new_unique()]);
+    let proof = tree.proof(&sender_key).unwrap();
+    send(
+        &mut svm,
+        &[set_merkle_root_instruction(&pubkey(&owner), tree.root(), 1)],
+        &[&owner],
+    )
+    .expect("set_merkle_root");
+
+    // A proof in the same transaction needs no account at all
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    send(
+        &mut svm,
+        &[
+            prove_membership_instruction(&sender_key, proof.clone()),
+            transfer,
+        ],
+        &[&sender],
+    )
+    .expect("transfer with proof");
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+
+    // A claim stands in for the proof on later transfers
+    send(
+        &mut svm,
+        &[claim_whitelist_instruction(&sender_key, proof)],
+        &[&sender],
+    )
+    .expect("claim_whitelist");
+    let claim: MerkleClaim = load(&svm, &merkle_claim_pda(&sender_key));
+    assert_eq!(claim.root, tree.root().unwrap());
+    assert!(svm
+        .get_account(&address(&whitelisted_user_pda(&sender_key)))
+        .is_none());
+
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    send(&mut svm, std::slice::from_ref(&transfer), &[&sender]).expect("transfer with claim");
+    assert_eq!(balance(&svm, &recipient_ata), 2 * TRANSFER_AMOUNT);
+
+    // Rotating the root revokes the claim
+    let rotated = MerkleTree::new(&[sender_key]);
+    send(
+        &mut svm,
+        &[set_merkle_root_instruction(
+            &pubkey(&owner),
+            rotated.root(),
+            1,
+        )],
+        &[&owner],
+    )
+    .expect("rotate root");
+    svm.expire_blockhash();
+    let result = send(&mut svm, std::slice::from_ref(&transfer), &[&sender]);
+    assert_custom_error(result, ErrorCode::NotWhiteListed);
+
+    // Claiming again under the new root restores it
+    send(
+        &mut svm,
+        &[claim_whitelist_instruction(
+            &sender_key,
+            rotated.proof(&sender_key).unwrap(),
+        )],
+        &[&sender],
+    )
+    .expect("claim under the new root");
+    svm.expire_blockhash();
+    send(&mut svm, &[transfer], &[&sender]).expect("transfer after re-claim");
+    assert_eq!(balance(&svm, &recipient_ata), 3 * TRANSFER_AMOUNT);
+}
+
+#[test]
+fn only_the_owner_issues_tokens() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let operator = Keypair::new();
+    svm.airdrop(&operator.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(
+        &mut svm,
+        &[add_operator_ix(&owner, &pubkey(&operator))],
+        &[&owner],
+    )
+    .expect("add_operator");
+    let operator_ata = token_account(&mut svm, &owner, &operator, &mint);
+
+    // The Config PDA is the mint authority, so neither a direct mint_to nor an operator works
+    let direct = anchor_spl::token_2022::spl_token_2022::instruction::mint_to(
+        &TOKEN_2022_PROGRAM_ID,
+        &mint,
+        &operator_ata,
+        &pubkey(&owner),
+        &[],
+        MINT_AMOUNT,
+    )
+    .unwrap();
+    assert!(send(&mut svm, &[direct], &[&owner]).is_err());
+    let result = send(
+        &mut svm,
+        &[issue_tokens_ix(&operator, &mint, &operator_ata)],
+        &[&operator],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+    assert_eq!(balance(&svm, &operator_ata), 0);
+
+    send(
+        &mut svm,
+        &[issue_tokens_ix(&owner, &mint, &operator_ata)],
+        &[&owner],
+    )
+    .expect("issue_tokens");
+    assert_eq!(balance(&svm, &operator_ata), MINT_AMOUNT);
+}
+
+#[test]
+fn operator_freezes_and_thaws_holders() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (sender, sender_ata) = funded_holder(&mut svm, &owner, &mint);
+    let recipient_ata = token_account(&mut svm, &owner, &Keypair::new(), &mint);
+    let operator = Keypair::new();
+    let stranger = Keypair::new();
+    svm.airdrop(&operator.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    send(
+        &mut svm,
+        &[
+            add_operator_ix(&owner, &pubkey(&operator)),
+            add_to_whitelist_ix(&owner, &pubkey(&sender), kyc(1)),
+        ],
+        &[&owner],
+    )
+    .expect("add operator and whitelist sender");
+
+    let result = send(
+        &mut svm,
+        &[freeze_holder_ix(&stranger, &sender_ata)],
+        &[&stranger],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    send(
+        &mut svm,
+        &[freeze_holder_ix(&operator, &sender_ata)],
+        &[&operator],
+    )
+    .expect("freeze_holder");
+    assert!(is_frozen(&svm, &sender_ata));
+    let transfer = transfer_ix(&svm, &sender, &mint, &sender_ata, &recipient_ata);
+    assert!(send(&mut svm, std::slice::from_ref(&transfer), &[&sender]).is_err());
+
+    send(
+        &mut svm,
+        &[thaw_holder_ix(&operator, &sender_ata)],
+        &[&operator],
+    )
+    .expect("thaw_holder");
+    assert!(!is_frozen(&svm, &sender_ata));
+    svm.expire_blockhash();
+    send(&mut svm, &[transfer], &[&sender]).expect("transfer after thaw");
+    assert_eq!(balance(&svm, &recipient_ata), TRANSFER_AMOUNT);
+}
+
+#[test]
+fn clawback_moves_tokens_out_of_a_frozen_account() {
+    let (mut svm, owner) = setup();
+    let mint = setup_mint(&mut svm, &owner);
+    let (holder, holder_ata) = funded_holder(&mut svm, &owner, &mint);
+    let treasury_ata = token_account(&mut svm, &owner, &owner, &mint);
+    send(
+        &mut svm,
+        &[freeze_holder_ix(&owner, &holder_ata)],
+        &[&owner],
+    )
+    .expect("freeze_holder");
+
+    // Clawback is owner only, not an operator or the holder
+    let result = send(
+        &mut svm,
+        &[clawback_ix(
+            &holder,
+            &holder_ata,
+            &treasury_ata,
+            TRANSFER_AMOUNT,
+        )],
+        &[&holder],
+    );
+    assert_custom_error(result, ErrorCode::Unauthorized);
+
+    send(
+        &mut svm,
+        &[clawback_ix(
+            &owner,
+            &holder_ata,
+            &treasury_ata,
+            TRANSFER_AMOUNT,
+        )],
+        &[&owner],
+    )
+    .expect("clawback");
+    assert_eq!(balance(&svm, &holder_ata), MINT_AMOUNT - TRANSFER_AMOUNT);
+    assert_eq!(balance(&svm, &treasury_ata), TRANSFER_AMOUNT);
+    // Burned and re-minted in one instruction: the holder stays frozen and supply is unchanged
+    assert!(is_frozen(&svm, &holder_ata));
+    let account = svm.get_account(&address(&mint)).unwrap();
+    let supply = StateWithExtensions::<Mint>::unpack(&account.data)
+        .unwrap()
+        .base
+        .supply;
+    assert_eq!(supply, MINT_AMOUNT);
+}
+
+// --- Setup ---
+
+fn setup() -> (LiteSVM, Keypair) {
+    let program = std::fs::read(PROGRAM_PATH)
+        .unwrap_or_else(|_| panic!("{PROGRAM_PATH} not found, run `anchor build` first"));
+
+    let mut svm = LiteSVM::new();
+    let owner = Keypair::new();
+    svm.airdrop(&owner.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+    add_upgradeable_program(&mut svm, &program, &pubkey(&owner));
+    (svm, owner)
+}
+
+/// Deploys the program through the upgradeable loader, as `anchor deploy` does, so that
+/// `init_config` can read `upgrade_authority` from its ProgramData account.
+fn add_upgradeable_program(svm: &mut LiteSVM, program: &[u8], upgrade_authority: &Pubkey) {
+    let program_data = program_data_pda();
+    // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address }, then the ELF
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority.as_ref());
+    data.extend_from_slice(program);
+    svm.set_account(
+        address(&program_data),
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: address(&bpf_loader_upgradeable::ID),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+
+    // UpgradeableLoaderState::Program { programdata_address }
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(program_data.as_ref());
+    svm.set_account(
+        address(&whitelist_transfer_hook::ID),
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: address(&bpf_loader_upgradeable::ID),
+            executable: true,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+}
+
+fn add_escrow_program(svm: &mut LiteSVM) {
+    let program = std::fs::read(ESCROW_PROGRAM_PATH).unwrap_or_else(|_| {
+        panic!("{ESCROW_PROGRAM_PATH} not found, run `anchor build` in escrow-litesvm first")
+    });
+    svm.add_program(address(&anchor_escrow::ID), &program)
+        .unwrap();
+}
+
+fn add_tuktuk_program(svm: &mut LiteSVM) {
+    let program = std::fs::read(TUKTUK_PROGRAM_PATH).unwrap_or_else(|_| {
+        panic!("{TUKTUK_PROGRAM_PATH} not found, run `anchor build` in tuktuk-mock first")
+    });
+    svm.add_program(address(&tuktuk::ID), &program).unwrap();
+}
+
+/// Config, program mint without optional extensions, and its ExtraAccountMetaList.
+fn setup_mint(svm: &mut LiteSVM, owner: &Keypair) -> Pubkey {
+    setup_mint_with_mode(svm, owner, WhitelistMode::PerUser)
+}
+
+fn setup_mint_with_mode(svm: &mut LiteSVM, owner: &Keypair, mode: WhitelistMode) -> Pubkey {
+    let mint = mint_pda();
+    send(
+        svm,
+        &[
+            init_config_with_mode_ix(owner, mode),
+            init_mint_ix(owner, MintExtensions::default()),
+            initialize_transfer_hook_ix(owner, &mint),
+        ],
+        &[owner],
+    )
+    .expect("mint setup");
+    mint
+}
+
+/// New keypair holding `MINT_AMOUNT` tokens.
+fn funded_holder(svm: &mut LiteSVM, owner: &Keypair, mint: &Pubkey) -> (Keypair, Pubkey) {
+    let holder = Keypair::new();
+    svm.airdrop(&holder.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let ata = token_account(svm, owner, &holder, mint);
+
+    send(svm, &[issue_tokens_ix(owner, mint, &ata)], &[owner]).expect("issue_tokens");
+
+    (holder, ata)
+}
+
+fn token_account(svm: &mut LiteSVM, payer: &Keypair, holder: &Keypair, mint: &Pubkey) -> Pubkey {
+    let ix = create_associated_token_account(
+        &pubkey(payer),
+        &pubkey(holder),
+        mint,
+        &TOKEN_2022_PROGRAM_ID,
+    );
+    send(svm, &[ix], &[payer]).expect("create token account");
+    get_associated_token_address_with_program_id(&pubkey(holder), mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+// --- Instructions ---
+
+fn init_config_ix(owner: &Keypair) -> AnchorInstruction {
+    init_config_with_mode_ix(owner, WhitelistMode::PerUser)
+}
+
+fn init_config_with_mode_ix(owner: &Keypair, mode: WhitelistMode) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::InitConfig {
+            owner: pubkey(owner),
+            program_data: program_data_pda(),
+            config: config_pda(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::InitConfig { mode }.data(),
+    }
+}
+
+fn init_mint_ix(owner: &Keypair, extensions: MintExtensions) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::TokenFactory {
+            owner: pubkey(owner),
+            config: config_pda(),
+            mint: mint_pda(),
+            system_program: system_program::ID,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::InitMint {
+            decimals: DECIMALS,
+            extensions,
+        }
+        .data(),
+    }
+}
+
+fn update_token_metadata_ix(
+    owner: &Keypair,
+    field: MetadataField,
+    value: &str,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::UpdateTokenMetadata {
+            owner: pubkey(owner),
+            config: config_pda(),
+            mint: mint_pda(),
+            system_program: system_program::ID,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::UpdateTokenMetadata {
+            field,
+            value: value.to_string(),
+        }
+        .data(),
+    }
+}
+
+fn initialize_transfer_hook_ix(payer: &Keypair, mint: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::InitializeExtraAccountMetaList {
+            payer: pubkey(payer),
+            extra_account_meta_list: extra_account_meta_list_pda(mint),
+            mint: *mint,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::InitializeTransferHook {}.data(),
+    }
+}
+
+fn issue_tokens_ix(owner: &Keypair, mint: &Pubkey, destination: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::IssueTokens {
+            owner: pubkey(owner),
+            config: config_pda(),
+            mint: *mint,
+            destination_token: *destination,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::IssueTokens {
+            amount: MINT_AMOUNT,
+        }
+        .data(),
+    }
+}
+
+fn holder_operations(authority: &Keypair, token_account: &Pubkey) -> Vec<AnchorAccountMeta> {
+    whitelist_transfer_hook::accounts::HolderOperations {
+        authority: pubkey(authority),
+        config: config_pda(),
+        mint: mint_pda(),
+        token_account: *token_account,
+        token_program: TOKEN_2022_PROGRAM_ID,
+    }
+    .to_account_metas(None)
+}
+
+fn freeze_holder_ix(authority: &Keypair, token_account: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: holder_operations(authority, token_account),
+        data: whitelist_transfer_hook::instruction::FreezeHolder {}.data(),
+    }
+}
+
+fn thaw_holder_ix(authority: &Keypair, token_account: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: holder_operations(authority, token_account),
+        data: whitelist_transfer_hook::instruction::ThawHolder {}.data(),
+    }
+}
+
+fn clawback_ix(
+    owner: &Keypair,
+    source: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::Clawback {
+            owner: pubkey(owner),
+            config: config_pda(),
+            mint: mint_pda(),
+            source_token: *source,
+            destination_token: *destination,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::Clawback { amount }.data(),
+    }
+}
+
+fn add_operator_ix(owner: &Keypair, operator: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::OperatorOperations {
+            owner: pubkey(owner),
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::AddOperator {
+            operator: *operator,
+        }
+        .data(),
+    }
+}
+
+fn add_to_whitelist_ix(authority: &Keypair, user: &Pubkey, kyc: KycInfo) -> AnchorInstruction {
+    add_expiring_entry_ix(authority, user, kyc, None)
+}
+
+fn add_expiring_entry_ix(
+    authority: &Keypair,
+    user: &Pubkey,
+    kyc: KycInfo,
+    expiry: Option<EntryExpiry>,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::AddToWhitelist {
+            authority: pubkey(authority),
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            system_program: system_program::ID,
+            task_queue: None,
+            task_queue_authority: None,
+            task: None,
+            notice_task: None,
+            queue_authority: None,
+            tuktuk_program: None,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::AddToWhitelist {
+            user: *user,
+            kyc,
+            expiry,
+            task_id: None,
+            notice_task_id: None,
+        }
+        .data(),
+    }
+}
+
+/// `add_to_whitelist` that queues the entry's `expire_user` as task `task_id`, and its
+/// `notify_expiring` as task `notice_task_id`.
+fn add_scheduled_entry_ix(
+    authority: &Keypair,
+    user: &Pubkey,
+    task_queue: &Pubkey,
+    task_id: u16,
+    notice_task_id: Option<u16>,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::AddToWhitelist {
+            authority: pubkey(authority),
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            system_program: system_program::ID,
+            task_queue: Some(*task_queue),
+            task_queue_authority: Some(task_queue_authority_pda(
+                task_queue,
+                &queue_authority_pda(),
+            )),
+            task: Some(task_pda(task_queue, task_id)),
+            notice_task: notice_task_id.map(|id| task_pda(task_queue, id)),
+            queue_authority: Some(queue_authority_pda()),
+            tuktuk_program: Some(tuktuk::ID),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::AddToWhitelist {
+            user: *user,
+            kyc: kyc(1),
+            expiry: None,
+            task_id: Some(task_id),
+            notice_task_id,
+        }
+        .data(),
+    }
+}
+
+fn set_tier_rule_ix(owner: &Keypair, source_tier: u8, allowed: u8) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::SetTierRule {
+            owner: pubkey(owner),
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::SetTierRule {
+            source_tier,
+            allowed_destination_tiers: allowed,
+        }
+        .data(),
+    }
+}
+
+/// Attestation for `user` at `tier`, valid for `valid_for` seconds from the current clock.
+fn attestation(svm: &LiteSVM, user: &Keypair, tier: u8, valid_for: i64) -> KycAttestation {
+    KycAttestation {
+        program_id: whitelist_transfer_hook::ID,
+        user: pubkey(user),
+        kyc: kyc(tier),
+        nonce: 1,
+        expires_at: svm.get_sysvar::<Clock>().unix_timestamp + valid_for,
+    }
+}
+
+/// Ed25519 precompile instruction with `provider`'s signature, followed by `self_register`.
+fn self_register_ix(provider: &Keypair, attestation: &KycAttestation) -> Vec<AnchorInstruction> {
+    let signature = provider.sign_message(&attestation.message());
+    self_register_instructions(attestation, &pubkey(provider), &signature.into()).to_vec()
+}
+
+fn remove_from_whitelist_ix(
+    authority: &Keypair,
+    user: &Pubkey,
+    rent_payer: &Pubkey,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::RemoveFromWhitelist {
+            authority: pubkey(authority),
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            rent_payer: *rent_payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::RemoveFromWhitelist { user: *user }.data(),
+    }
+}
+
+fn expire_user_ix(user: &Pubkey) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::ExpireUser {
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::ExpireUser { user: *user }.data(),
+    }
+}
+
+fn set_max_entry_duration_ix(owner: &Keypair, max_entry_duration: i64) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::SetMaxEntryDuration {
+            owner: pubkey(owner),
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::SetMaxEntryDuration { max_entry_duration }
+            .data(),
+    }
+}
+
+fn renew_whitelist_ix(
+    authority: &Keypair,
+    user: &Pubkey,
+    expiry: EntryExpiry,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::RenewWhitelist {
+            authority: pubkey(authority),
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            system_program: system_program::ID,
+            task_queue: None,
+            task_queue_authority: None,
+            task: None,
+            notice_task: None,
+            queue_authority: None,
+            tuktuk_program: None,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::RenewWhitelist {
+            user: *user,
+            expiry,
+            task_id: None,
+            notice_task_id: None,
+        }
+        .data(),
+    }
+}
+
+/// `renew_whitelist` that queues the renewed entry's `expire_user` as task `task_id`.
+fn renew_scheduled_ix(
+    authority: &Keypair,
+    user: &Pubkey,
+    expiry: EntryExpiry,
+    task_queue: &Pubkey,
+    task_id: u16,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::RenewWhitelist {
+            authority: pubkey(authority),
+            config: config_pda(),
+            whitelisted_user: whitelisted_user_pda(user),
+            system_program: system_program::ID,
+            task_queue: Some(*task_queue),
+            task_queue_authority: Some(task_queue_authority_pda(
+                task_queue,
+                &queue_authority_pda(),
+            )),
+            task: Some(task_pda(task_queue, task_id)),
+            notice_task: None,
+            queue_authority: Some(queue_authority_pda()),
+            tuktuk_program: Some(tuktuk::ID),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::RenewWhitelist {
+            user: *user,
+            expiry,
+            task_id: Some(task_id),
+            notice_task_id: None,
+        }
+        .data(),
+    }
+}
+
+fn vault_whitelist_ix(
+    admin: &Keypair,
+    vault: &Pubkey,
+    user: &Pubkey,
+    add: bool,
+) -> AnchorInstruction {
+    let (accounts, data) = if add {
+        (
+            whitelist_transfer_hook::accounts::AddToVaultWhitelist {
+                admin: pubkey(admin),
+                vault: *vault,
+                position: vault_position_pda(vault, user),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            whitelist_transfer_hook::instruction::AddToVaultWhitelist { user: *user }.data(),
+        )
+    } else {
+        (
+            whitelist_transfer_hook::accounts::RemoveFromVaultWhitelist {
+                admin: pubkey(admin),
+                vault: *vault,
+                position: vault_position_pda(vault, user),
+            }
+            .to_account_metas(None),
+            whitelist_transfer_hook::instruction::RemoveFromVaultWhitelist { user: *user }.data(),
+        )
+    };
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts,
+        data,
+    }
+}
+
+/// Writes a `Whitelist` account as created before `VaultPosition`s replaced it.
+fn set_legacy_vault_whitelist(svm: &mut LiteSVM, entries: &[WhitelistEntry]) {
+    let (_, bump) = Pubkey::find_program_address(&[b"whitelist"], &whitelist_transfer_hook::ID);
+    let mut data = Vec::new();
+    Whitelist {
+        address: entries.to_vec(),
+        bump,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    svm.set_account(
+        address(&vault_whitelist_pda()),
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: address(&whitelist_transfer_hook::ID),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+}
+
+fn initialize_vault_ix(admin: &Keypair, mint: &Pubkey, vault_id: u16) -> AnchorInstruction {
+    let vault = vault_pda(mint, vault_id);
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::InitializeVault {
+            admin: pubkey(admin),
+            config: config_pda(),
+            mint: *mint,
+            vault,
+            vault_authority: vault_authority_pda(&vault),
+            token_account: vault_token_account_pda(&vault),
+            system_program: system_program::ID,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::InitializeVault { vault_id }.data(),
+    }
+}
+
+/// Token 2022 mint without extensions whose mint authority is the authority of its
+/// `HOOK_VAULT_ID` vault.
+fn vault_authority_mint(svm: &mut LiteSVM) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(vault_authority_pda(&vault_pda(&mint, HOOK_VAULT_ID))),
+        supply: 0,
+        decimals: DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    svm.set_account(
+        address(&mint),
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: address(&TOKEN_2022_PROGRAM_ID),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    mint
+}
+
+fn mint_token_ix(admin: &Keypair, mint: &Pubkey, destination: &Pubkey) -> AnchorInstruction {
+    let vault = vault_pda(mint, HOOK_VAULT_ID);
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::MintToken {
+            admin: pubkey(admin),
+            mint: *mint,
+            destination_token_account: *destination,
+            vault,
+            vault_authority: vault_authority_pda(&vault),
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::MintToken {
+            amount: MINT_AMOUNT,
+        }
+        .data(),
+    }
+}
+
+/// `deposit` `amount` tokens into the `vault_id` vault of `mint`, or `withdraw` `amount` shares
+/// from it.
+fn vault_transfer_ix(
+    user: &Keypair,
+    mint: &Pubkey,
+    vault_id: u16,
+    user_token_account: &Pubkey,
+    amount: u64,
+    deposit: bool,
+) -> AnchorInstruction {
+    let vault = vault_pda(mint, vault_id);
+    let (accounts, data) = if deposit {
+        (
+            whitelist_transfer_hook::accounts::Deposit {
+                user: pubkey(user),
+                config: config_pda(),
+                mint: *mint,
+                user_token_account: *user_token_account,
+                vault,
+                vault_authority: vault_authority_pda(&vault),
+                vault_token_account: vault_token_account_pda(&vault),
+                position: vault_position_pda(&vault, &pubkey(user)),
+                token_program: TOKEN_2022_PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            whitelist_transfer_hook::instruction::Deposit { amount }.data(),
+        )
+    } else {
+        (
+            whitelist_transfer_hook::accounts::Withdraw {
+                user: pubkey(user),
+                config: config_pda(),
+                mint: *mint,
+                user_token_account: *user_token_account,
+                vault,
+                vault_authority: vault_authority_pda(&vault),
+                vault_token_account: vault_token_account_pda(&vault),
+                position: vault_position_pda(&vault, &pubkey(user)),
+                token_program: TOKEN_2022_PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            whitelist_transfer_hook::instruction::Withdraw { shares: amount }.data(),
+        )
+    };
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts,
+        data,
+    }
+}
+
+fn distribute_rewards_ix(
+    admin: &Keypair,
+    mint: &Pubkey,
+    admin_token_account: &Pubkey,
+    amount: u64,
+) -> AnchorInstruction {
+    let vault = vault_pda(mint, HOOK_VAULT_ID);
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::DistributeRewards {
+            admin: pubkey(admin),
+            config: config_pda(),
+            mint: *mint,
+            admin_token_account: *admin_token_account,
+            vault,
+            vault_token_account: vault_token_account_pda(&vault),
+            token_program: TOKEN_2022_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::DistributeRewards { amount }.data(),
+    }
+}
+
+fn init_trading_schedule_ix(
+    owner: &Keypair,
+    windows: Vec<TradingWindow>,
+    blackouts: Vec<BlackoutPeriod>,
+) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::InitTradingSchedule {
+            owner: pubkey(owner),
+            config: config_pda(),
+            trading_schedule: trading_schedule_pda(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::InitTradingSchedule { windows, blackouts }
+            .data(),
+    }
+}
+
+fn set_trading_schedule_ix(owner: &Keypair, enabled: bool) -> AnchorInstruction {
+    AnchorInstruction {
+        program_id: whitelist_transfer_hook::ID,
+        accounts: whitelist_transfer_hook::accounts::TradingScheduleOperations {
+            owner: pubkey(owner),
+            config: config_pda(),
+            trading_schedule: trading_schedule_pda(),
+        }
+        .to_account_metas(None),
+        data: whitelist_transfer_hook::instruction::SetTradingSchedule { enabled }.data(),
+    }
+}
+
+/// `transfer_checked` with the hook's extra accounts resolved from the on-chain
+/// ExtraAccountMetaList, the way a wallet would build it.
+fn transfer_ix(
+    svm: &LiteSVM,
+    sender: &Keypair,
+    mint: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+) -> AnchorInstruction {
+    transfer_checked_instruction(
+        source,
+        mint,
+        destination,
+        &pubkey(sender),
+        TRANSFER_AMOUNT,
+        DECIMALS,
+        |key| svm.get_account(&address(key)).map(|account| account.data),
+    )
+    .expect("resolve extra accounts")
+}
+
+fn escrow_pda(maker: &Keypair) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"escrow",
+            pubkey(maker).as_ref(),
+            &ESCROW_SEED.to_le_bytes(),
+        ],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+fn escrow_vault(maker: &Keypair, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(&escrow_pda(maker), mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+/// Escrows `TRANSFER_AMOUNT` of the hook mint, asking for the same mint in return.
+fn escrow_make_ix(maker: &Keypair, mint: &Pubkey) -> AnchorInstruction {
+    let mut accounts = anchor_escrow::accounts::Make {
+        maker: pubkey(maker),
+        mint_a: *mint,
+        mint_b: *mint,
+        maker_ata_a: get_associated_token_address_with_program_id(
+            &pubkey(maker),
+            mint,
+            &TOKEN_2022_PROGRAM_ID,
+        ),
+        escrow: escrow_pda(maker),
+        vault: escrow_vault(maker, mint),
+        associated_token_program: associated_token::ID,
+        token_program: TOKEN_2022_PROGRAM_ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(escrow_hook_accounts(
+        &pubkey(maker),
+        &escrow_pda(maker),
+        mint,
+    ));
+
+    AnchorInstruction {
+        program_id: anchor_escrow::ID,
+        accounts,
+        data: anchor_escrow::instruction::Make {
+            seed: ESCROW_SEED,
+            deposit: TRANSFER_AMOUNT,
+            receive: TRANSFER_AMOUNT,
+        }
+        .data(),
+    }
+}
+
+/// Returns the escrowed tokens to the maker; the escrow PDA signs the transfer out of the vault.
+fn escrow_refund_ix(maker: &Keypair, mint: &Pubkey) -> AnchorInstruction {
+    let mut accounts = anchor_escrow::accounts::Refund {
+        maker: pubkey(maker),
+        mint_a: *mint,
+        maker_ata_a: get_associated_token_address_with_program_id(
+            &pubkey(maker),
+            mint,
+            &TOKEN_2022_PROGRAM_ID,
+        ),
+        escrow: escrow_pda(maker),
+        vault: escrow_vault(maker, mint),
+        token_program: TOKEN_2022_PROGRAM_ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(escrow_hook_accounts(
+        &escrow_pda(maker),
+        &pubkey(maker),
+        mint,
+    ));
+
+    AnchorInstruction {
+        program_id: anchor_escrow::ID,
+        accounts,
+        data: anchor_escrow::instruction::Refund {}.data(),
+    }
+}
+
+/// Remaining accounts the escrow forwards to Token 2022 for the hook: the ExtraAccountMetaList
+/// entries, the hook program and the list itself. Listed by hand because `make` creates the
+/// vault in the same instruction, so the resolver cannot read its owner yet.
+fn escrow_hook_accounts(
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    mint: &Pubkey,
+) -> Vec<AnchorAccountMeta> {
+    let vault = vault_pda(mint, HOOK_VAULT_ID);
+    vec![
+        AnchorAccountMeta::new_readonly(whitelisted_user_pda(source_owner), false),
+        AnchorAccountMeta::new_readonly(config_pda(), false),
+        AnchorAccountMeta::new_readonly(sysvar::instructions::ID, false),
+        AnchorAccountMeta::new_readonly(whitelisted_user_pda(destination_owner), false),
+        AnchorAccountMeta::new_readonly(vault_whitelist_pda(), false),
+        AnchorAccountMeta::new_readonly(vault, false),
+        AnchorAccountMeta::new_readonly(transfer_log_pda(mint), false),
+        AnchorAccountMeta::new_readonly(trading_schedule_pda(), false),
+        AnchorAccountMeta::new_readonly(vault_position_pda(&vault, source_owner), false),
+        AnchorAccountMeta::new_readonly(vault_position_pda(&vault, destination_owner), false),
+        AnchorAccountMeta::new_readonly(merkle_claim_pda(source_owner), false),
+        AnchorAccountMeta::new_readonly(merkle_claim_pda(destination_owner), false),
+        AnchorAccountMeta::new_readonly(whitelist_transfer_hook::ID, false),
+        AnchorAccountMeta::new_readonly(extra_account_meta_list_pda(mint), false),
+    ]
+}
+
+// --- Helpers ---
+
+fn kyc(tier: u8) -> KycInfo {
+    KycInfo {
+        tier,
+        country: *b"DE",
+        attestation: [tier; 32],
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn send(
+    svm: &mut LiteSVM,
+    instructions: &[AnchorInstruction],
+    signers: &[&Keypair],
+) -> TransactionResult {
+    let instructions: Vec<Instruction> = instructions.iter().map(convert_instruction).collect();
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&signers[0].pubkey()),
+        signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+}
+
+fn assert_custom_error(result: TransactionResult, error: ErrorCode) {
+    let failed = result.expect_err("transaction should fail");
+    let expected = format!("Custom({})", u32::from(error));
+    assert!(
+        format!("{:?}", failed.err).contains(&expected),
+        "expected {expected}, got {:?}\n{}",
+        failed.err,
+        failed.meta.logs.join("\n")
+    );
+}
+
+/// Sets the clock and starts a new blockhash, so an identical transaction can be resent.
+fn warp_to(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+}
+
+fn load<T: AccountDeserialize>(svm: &LiteSVM, key: &Pubkey) -> T {
+    let account = svm.get_account(&address(key)).unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn log_is_writable(transfer: &AnchorInstruction, mint: &Pubkey) -> bool {
+    transfer
+        .accounts
+        .iter()
+        .find(|meta| meta.pubkey == transfer_log_pda(mint))
+        .expect("transfer log account")
+        .is_writable
+}
+
+fn is_frozen(svm: &LiteSVM, token_account: &Pubkey) -> bool {
+    let account = svm.get_account(&address(token_account)).unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+        .unwrap()
+        .base
+        .is_frozen()
+}
+
+fn balance(svm: &LiteSVM, token_account: &Pubkey) -> u64 {
+    let account = svm.get_account(&address(token_account)).unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+fn convert_instruction(ix: &AnchorInstruction) -> Instruction {
+    Instruction {
+        program_id: address(&ix.program_id),
+        accounts: ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: address(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: ix.data.clone(),
+    }
+}
+
+fn pubkey(keypair: &Keypair) -> Pubkey {
+    Pubkey::new_from_array(keypair.pubkey().to_bytes())
+}
+
+fn address(pubkey: &Pubkey) -> Address {
+    Address::new_from_array(pubkey.to_bytes())
+}
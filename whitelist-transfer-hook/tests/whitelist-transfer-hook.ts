@@ -10,18 +10,19 @@ import {
   createInitializeTransferHookInstruction,
   createAssociatedTokenAccountInstruction,
   createMintToInstruction,
-  createTransferCheckedInstruction,
+  createTransferCheckedWithTransferHookInstruction,
+  getAccount,
 } from "@solana/spl-token";
 import {
   Keypair,
   PublicKey,
-  SendTransactionError,
   SystemProgram,
   Transaction,
   sendAndConfirmTransaction
 } from '@solana/web3.js';
 import { WhitelistTransferHook } from "../target/types/whitelist_transfer_hook";
 import { assert } from "chai";
+import { createHash } from "crypto";
 
 describe("whitelist-transfer-hook", () => {
   const provider = anchor.AnchorProvider.env();
@@ -34,6 +35,10 @@ describe("whitelist-transfer-hook", () => {
   const mint2022 = Keypair.generate();
   const user = Keypair.generate();
   const recipient = Keypair.generate();
+  const operator = Keypair.generate();
+  const operatorUser = Keypair.generate();
+  const merkleUser = Keypair.generate();
+  const merkleOther = Keypair.generate();
 
   // PDA helpers
   const getPda = (prefix: string, pubkey: PublicKey) =>
@@ -42,10 +47,32 @@ describe("whitelist-transfer-hook", () => {
   const extraAccountMetaListPDA = getPda("extra-account-metas", mint2022.publicKey);
   const whitelistedUserPDA = getPda("whitelisted_user", user.publicKey);
   const configPda = PublicKey.findProgramAddressSync(
-    [Buffer.from("config"), wallet.payer.publicKey.toBuffer()],
+    [Buffer.from("config")],
     program.programId
   )[0];
 
+  // Merkle helpers, same hashing as the program: sorted pairs, 0x00 leaf / 0x01 node prefix
+  const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+  const leafHash = (key: PublicKey) => sha256(Buffer.from([0]), key.toBuffer());
+  const hashPair = (a: Buffer, b: Buffer) =>
+    Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+  const merkleRoot = hashPair(leafHash(merkleUser.publicKey), leafHash(merkleOther.publicKey));
+
+  // Fails unless `action` throws the program error `code`; Anchor logs it as "Error Code: <code>"
+  const expectError = async (action: () => Promise<unknown>, code: string) => {
+    try {
+      await action();
+    } catch (err: any) {
+      const logs: string[] = err.logs ?? [];
+      assert.include(`${err}\n${logs.join("\n")}`, `Error Code: ${code}`);
+      return;
+    }
+    assert.fail(`Expected ${code}`);
+  };
+
+  // KYC record stored with a whitelist entry; the attestation stands in for a hash of the off-chain record
+  const kyc = (tier: number) => ({ tier, country: [...Buffer.from("DE")], attestation: Array(32).fill(tier) });
+
   // Token accounts
   const sourceTokenAccount = getAssociatedTokenAddressSync(
     mint2022.publicKey, user.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
@@ -53,6 +80,9 @@ describe("whitelist-transfer-hook", () => {
   const destinationTokenAccount = getAssociatedTokenAddressSync(
     mint2022.publicKey, recipient.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
   );
+  const merkleUserTokenAccount = getAssociatedTokenAddressSync(
+    mint2022.publicKey, merkleUser.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+  );
 
   before(async () => {
     // Fund the user
@@ -60,11 +90,26 @@ describe("whitelist-transfer-hook", () => {
       user.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL
     );
     await provider.connection.confirmTransaction(airdropSig, "confirmed");
+
+    const operatorAirdropSig = await provider.connection.requestAirdrop(
+      operator.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(operatorAirdropSig, "confirmed");
+
+    for (const key of [merkleUser.publicKey, merkleOther.publicKey]) {
+      const sig = await provider.connection.requestAirdrop(key, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig, "confirmed");
+    }
   });
 
   it("Initializes the Admin Config", async () => {
-    const tx = await program.methods.initConfig().accountsPartial({
-      admin: provider.publicKey,
+    // The provider wallet deployed the program, so it is the upgrade authority
+    const tx = await program.methods.initConfig({ perUser: {} }).accountsPartial({
+      owner: provider.publicKey,
+      programData: PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0],
       config: configPda,
       systemProgram: SystemProgram.programId,
     }).rpc();
@@ -72,13 +117,15 @@ describe("whitelist-transfer-hook", () => {
     console.log("\nAdmin config initialized:", configPda.toBase58(), "\nTransaction signature:", tx);
 
     const configAccount = await program.account.config.fetch(configPda);
-    assert.ok(configAccount.admin.equals(provider.publicKey), "Admin does not match provider");
+    assert.ok(configAccount.owner.equals(provider.publicKey), "Owner does not match provider");
+    assert.ok(configAccount.pendingOwner === null, "Pending owner should be empty");
+    assert.ok(configAccount.operators.length === 0, "Operators should be empty");
     assert.ok(typeof configAccount.bump === "number", "Config bump missing");
   });
 
   it("Add user to whitelist", async () => {
-    const tx = await program.methods.addToWhitelist(user.publicKey).accountsPartial({
-      admin: wallet.publicKey,
+    const tx = await program.methods.addToWhitelist(user.publicKey, kyc(1), null, null, null).accountsPartial({
+      authority: wallet.publicKey,
       config: configPda,
       whitelistedUser: whitelistedUserPDA,
       systemProgram: SystemProgram.programId,
@@ -90,30 +137,31 @@ describe("whitelist-transfer-hook", () => {
 
     assert.ok(whitelistedUserAccount.user.equals(user.publicKey), "Whitelist public key mismatch");
     assert.ok(typeof whitelistedUserAccount.bump === "number", "Whitelist bump missing");
+    assert.equal(whitelistedUserAccount.kyc.tier, 1, "KYC tier mismatch");
+    assert.equal(Buffer.from(whitelistedUserAccount.kyc.country).toString(), "DE", "Country mismatch");
+    assert.ok(whitelistedUserAccount.addedBy.equals(wallet.publicKey), "added_by mismatch");
   });
 
   it("Remove user from whitelist", async () => {
     const tx = await program.methods.removeFromWhitelist(user.publicKey).accountsPartial({
-      admin: wallet.publicKey,
+      authority: wallet.publicKey,
       config: configPda,
       whitelistedUser: whitelistedUserPDA,
+      rentPayer: wallet.publicKey,
       systemProgram: SystemProgram.programId,
     }).rpc();
 
     console.log("\nUser removed from whitelist:", user.publicKey.toBase58(), "\nTransaction signature:", tx);
 
-    let removedUserAccount = null;
-    try {
-      removedUserAccount = await program.account.whitelistedUser.fetch(whitelistedUserPDA);
-    } catch (_) {
-      removedUserAccount = null;
-    }
-    assert.ok(removedUserAccount === null, "Whitelisted user account still exists after removal");
+    assert.isNull(
+      await program.account.whitelistedUser.fetchNullable(whitelistedUserPDA),
+      "Whitelisted user account still exists after removal"
+    );
   });
 
   it("Re-Add user to whitelist", async () => {
-    const tx = await program.methods.addToWhitelist(user.publicKey).accountsPartial({
-      admin: wallet.publicKey,
+    const tx = await program.methods.addToWhitelist(user.publicKey, kyc(1), null, null, null).accountsPartial({
+      authority: wallet.publicKey,
       config: configPda,
       whitelistedUser: whitelistedUserPDA,
       systemProgram: SystemProgram.programId,
@@ -127,6 +175,151 @@ describe("whitelist-transfer-hook", () => {
     console.log("\nUser re-added and verified on whitelist:", user.publicKey.toBase58());
   });
 
+  it("Add an operator", async () => {
+    const tx = await program.methods.addOperator(operator.publicKey).accountsPartial({
+      owner: wallet.publicKey,
+      config: configPda,
+    }).rpc();
+
+    console.log("\nOperator added:", operator.publicKey.toBase58(), "\nTransaction signature:", tx);
+
+    const configAccount = await program.account.config.fetch(configPda);
+    assert.ok(configAccount.operators.some((key) => key.equals(operator.publicKey)), "Operator missing from config");
+  });
+
+  it("Operator can add a user to the whitelist", async () => {
+    const operatorUserPDA = getPda("whitelisted_user", operatorUser.publicKey);
+    const tx = await program.methods.addToWhitelist(operatorUser.publicKey, kyc(1), null, null, null).accountsPartial({
+      authority: operator.publicKey,
+      config: configPda,
+      whitelistedUser: operatorUserPDA,
+      systemProgram: SystemProgram.programId,
+    }).signers([operator]).rpc();
+
+    console.log("\nUser whitelisted by operator:", operatorUser.publicKey.toBase58(), "\nTransaction signature:", tx);
+
+    const whitelistedUserAccount = await program.account.whitelistedUser.fetch(operatorUserPDA);
+    assert.ok(whitelistedUserAccount.user.equals(operatorUser.publicKey), "Operator whitelist entry mismatch");
+  });
+
+  it("Operator cannot change config", async () => {
+    await expectError(
+      () => program.methods.addOperator(Keypair.generate().publicKey).accountsPartial({
+        owner: operator.publicKey,
+        config: configPda,
+      }).signers([operator]).rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("Batch add and remove users", async () => {
+    const batchUsers = [Keypair.generate(), Keypair.generate(), Keypair.generate()].map((k) => k.publicKey);
+    const batchPdas = batchUsers.map((key) => getPda("whitelisted_user", key));
+    const remainingAccounts = batchPdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+
+    const addTx = await program.methods.batchAddToWhitelist(batchUsers, kyc(1)).accountsPartial({
+      authority: operator.publicKey,
+      config: configPda,
+      systemProgram: SystemProgram.programId,
+    }).remainingAccounts(remainingAccounts).signers([operator]).rpc();
+
+    console.log("\nBatch whitelisted", batchUsers.length, "users\nTransaction signature:", addTx);
+
+    for (const [i, pda] of batchPdas.entries()) {
+      const entry = await program.account.whitelistedUser.fetch(pda);
+      assert.ok(entry.user.equals(batchUsers[i]), "Batch whitelist entry mismatch");
+    }
+
+    // Each entry is followed by its rent payer, the operator that added it
+    const removeAccounts = remainingAccounts.flatMap((entry) => [
+      entry,
+      { pubkey: operator.publicKey, isSigner: false, isWritable: true },
+    ]);
+    const removeTx = await program.methods.batchRemoveFromWhitelist(batchUsers).accountsPartial({
+      authority: operator.publicKey,
+      config: configPda,
+      systemProgram: SystemProgram.programId,
+    }).remainingAccounts(removeAccounts).signers([operator]).rpc();
+
+    console.log("\nBatch removed", batchUsers.length, "users\nTransaction signature:", removeTx);
+
+    for (const pda of batchPdas) {
+      assert.isNull(await provider.connection.getAccountInfo(pda), "Batch entry should be closed");
+    }
+  });
+
+  it("Transfer ownership with propose and accept", async () => {
+    await program.methods.proposeOwner(operator.publicKey).accountsPartial({
+      owner: wallet.publicKey,
+      config: configPda,
+    }).rpc();
+
+    let configAccount = await program.account.config.fetch(configPda);
+    assert.ok(configAccount.pendingOwner.equals(operator.publicKey), "Pending owner mismatch");
+
+    await program.methods.acceptOwner().accountsPartial({
+      newOwner: operator.publicKey,
+      config: configPda,
+    }).signers([operator]).rpc();
+
+    configAccount = await program.account.config.fetch(configPda);
+    assert.ok(configAccount.owner.equals(operator.publicKey), "Ownership was not transferred");
+
+    // Hand ownership back so the remaining tests run as the provider wallet
+    await program.methods.proposeOwner(wallet.publicKey).accountsPartial({
+      owner: operator.publicKey,
+      config: configPda,
+    }).signers([operator]).rpc();
+    await program.methods.acceptOwner().accountsPartial({
+      newOwner: wallet.publicKey,
+      config: configPda,
+    }).rpc();
+
+    configAccount = await program.account.config.fetch(configPda);
+    assert.ok(configAccount.owner.equals(wallet.publicKey), "Ownership was not returned");
+  });
+
+  it("Set the Merkle root", async () => {
+    const tx = await program.methods.setMerkleRoot([...merkleRoot], 1).accountsPartial({
+      owner: wallet.publicKey,
+      config: configPda,
+    }).rpc();
+
+    console.log("\nMerkle root set:", merkleRoot.toString("hex"), "\nTransaction signature:", tx);
+
+    const configAccount = await program.account.config.fetch(configPda);
+    assert.ok(Buffer.from(configAccount.merkleRoot).equals(merkleRoot), "Merkle root mismatch");
+    assert.equal(configAccount.merkleTier, 1, "Merkle tier mismatch");
+  });
+
+  it("Claim a whitelist entry with a Merkle proof", async () => {
+    const proof = [[...leafHash(merkleUser.publicKey)]];
+    const tx = await program.methods.claimWhitelist(proof).accountsPartial({
+      user: merkleOther.publicKey,
+      config: configPda,
+      merkleClaim: getPda("merkle_claim", merkleOther.publicKey),
+      systemProgram: SystemProgram.programId,
+    }).signers([merkleOther]).rpc();
+
+    console.log("\nWhitelist claimed:", merkleOther.publicKey.toBase58(), "\nTransaction signature:", tx);
+
+    const claim = await program.account.merkleClaim.fetch(getPda("merkle_claim", merkleOther.publicKey));
+    assert.ok(claim.user.equals(merkleOther.publicKey), "Claimed entry mismatch");
+    assert.ok(Buffer.from(claim.root).equals(merkleRoot), "Claim should record the root");
+  });
+
+  it("Claim fails with a wrong proof", async () => {
+    await expectError(
+      () => program.methods.claimWhitelist([[...leafHash(Keypair.generate().publicKey)]]).accountsPartial({
+        user: merkleUser.publicKey,
+        config: configPda,
+        merkleClaim: getPda("merkle_claim", merkleUser.publicKey),
+        systemProgram: SystemProgram.programId,
+      }).signers([merkleUser]).rpc(),
+      "InvalidMerkleProof"
+    );
+  });
+
   it("Create Mint Account with Transfer Hook Extension", async () => {
     const extensions = [ExtensionType.TransferHook];
     const mintLen = getMintLen(extensions);
@@ -208,43 +401,99 @@ describe("whitelist-transfer-hook", () => {
     console.log("Transaction Signature:", tx);
   });
 
-  it("Transfer Token with Extra Account Meta", async () => {
-    const amount = 1 * 10 ** 9;
-
-    const transferInstruction = createTransferCheckedInstruction(
-      sourceTokenAccount,
+  // Resolves every extra account from the mint's ExtraAccountMetaList, as wallets do
+  const hookTransfer = (source: PublicKey, destination: PublicKey, owner: PublicKey, amount: number) =>
+    createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      source,
       mint2022.publicKey,
-      destinationTokenAccount,
-      user.publicKey,
-      amount,
+      destination,
+      owner,
+      BigInt(amount),
       9,
       [],
+      "confirmed",
       TOKEN_2022_PROGRAM_ID
     );
 
-    transferInstruction.keys.push(
-      { pubkey: extraAccountMetaListPDA, isSigner: false, isWritable: false },
-      { pubkey: whitelistedUserPDA, isSigner: false, isWritable: false },
-      { pubkey: program.programId, isSigner: false, isWritable: false }
+  it("Transfer Token with Extra Account Meta", async () => {
+    const amount = 1 * 10 ** 9;
+
+    const transferInstruction = await hookTransfer(
+      sourceTokenAccount, destinationTokenAccount, user.publicKey, amount
+    );
+    assert.ok(
+      transferInstruction.keys.some((key) => key.pubkey.equals(whitelistedUserPDA)),
+      "Resolved accounts should include the sender's WhitelistedUser PDA"
+    );
+
+    const txSig = await sendAndConfirmTransaction(
+      provider.connection, new Transaction().add(transferInstruction), [user]
     );
+    console.log("\nTransfer Signature:", txSig);
 
-    const transaction = new Transaction().add(transferInstruction);
-    try {
-      const txSig = await sendAndConfirmTransaction(
-        provider.connection, transaction, [user], { skipPreflight: false }
-      );
-      console.log("\nTransfer Signature:", txSig);
-    } catch (error: any) {
-      console.error(error);
-      if (error?.meta?.logMessages) {
-        error.meta.logMessages.forEach((log: string, i: number) => console.error(`  ${i}: ${log}`));
-      }
-      if (error instanceof SendTransactionError && error.logs) {
-        console.error("\nTransaction failed:", error.logs[6]);
-        error.logs.forEach((log, i) => console.error(`  ${i}: ${log}`));
-      } else {
-        console.error("\nUnexpected error:", error);
-      }
-    }
+    const destination = await getAccount(
+      provider.connection, destinationTokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(Number(destination.amount), amount, "Recipient balance mismatch");
+  });
+
+  it("Transfer fails for an owner without a whitelist entry", async () => {
+    const outsider = Keypair.generate();
+    const outsiderTokenAccount = getAssociatedTokenAddressSync(
+      mint2022.publicKey, outsider.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    await sendAndConfirmTransaction(provider.connection, new Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        wallet.publicKey, outsiderTokenAccount, outsider.publicKey,
+        mint2022.publicKey, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        mint2022.publicKey, outsiderTokenAccount, wallet.publicKey,
+        10 ** 9, [], TOKEN_2022_PROGRAM_ID
+      )
+    ), [wallet.payer]);
+
+    const transferInstruction = await hookTransfer(
+      outsiderTokenAccount, destinationTokenAccount, outsider.publicKey, 10 ** 9
+    );
+    await expectError(
+      () => sendAndConfirmTransaction(
+        provider.connection, new Transaction().add(transferInstruction), [wallet.payer, outsider]
+      ),
+      "NotWhiteListed"
+    );
+  });
+
+  it("Transfer with a Merkle proof instead of a WhitelistedUser account", async () => {
+    const amount = 1 * 10 ** 9;
+
+    await sendAndConfirmTransaction(provider.connection, new Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        wallet.publicKey, merkleUserTokenAccount, merkleUser.publicKey,
+        mint2022.publicKey, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        mint2022.publicKey, merkleUserTokenAccount, wallet.publicKey,
+        amount, [], TOKEN_2022_PROGRAM_ID
+      )
+    ), [wallet.payer]);
+
+    const proveIx = await program.methods
+      .proveMembership(merkleUser.publicKey, [[...leafHash(merkleOther.publicKey)]])
+      .accountsPartial({ config: configPda })
+      .instruction();
+
+    const transferInstruction = await hookTransfer(
+      merkleUserTokenAccount, destinationTokenAccount, merkleUser.publicKey, amount
+    );
+
+    const txSig = await sendAndConfirmTransaction(
+      provider.connection, new Transaction().add(proveIx, transferInstruction), [merkleUser]
+    );
+    console.log("\nMerkle transfer Signature:", txSig);
+
+    const balance = await provider.connection.getTokenAccountBalance(merkleUserTokenAccount);
+    assert.equal(balance.value.amount, "0", "Tokens were not transferred");
   });
-})
\ No newline at end of file
+})